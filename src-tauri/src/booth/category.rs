@@ -0,0 +1,86 @@
+/// (canonical Japanese name, English name, Korean name)
+///
+/// The Japanese name is the key we store: browse URLs and the frontend's
+/// `i18n.categories` tables are keyed by it too.
+const CATEGORY_NAMES: &[(&str, &str, &str)] = &[
+    ("3Dモデル", "3D Models", "3D 모델"),
+    ("3Dキャラクター", "3D Characters", "3D 캐릭터"),
+    ("3D衣装", "3D Clothing", "3D 의상"),
+    ("3D小道具", "3D Props", "3D 소품"),
+    ("3Dテクスチャ", "3D Textures", "3D 텍스처"),
+    ("3Dモデル（その他）", "3D Models (Other)", "3D 모델 (기타)"),
+    ("3D装飾品", "3D Accessories", "3D 장식품"),
+    (
+        "3D環境・ワールド",
+        "3D Environments/Worlds",
+        "3D 환경・월드",
+    ),
+    (
+        "3Dモーション・アニメーション",
+        "3D Motions/Animations",
+        "3D 모션・애니메이션",
+    ),
+    ("3Dツール・システム", "3D Tools/Systems", "3D 툴・시스템"),
+    ("VRoid", "VRoid", "VRoid"),
+    ("イラスト", "Illustrations", "일러스트"),
+    ("ソフトウェア", "Software", "소프트웨어"),
+    ("素材データ", "Materials", "소재 데이터"),
+];
+
+/// Fold case, width and the separators Booth varies between locales
+/// ("3D Environments & Worlds", "3D環境/ワールド", "3d 의상"...).
+fn fold(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '（' => '(',
+            '）' => ')',
+            _ => c,
+        })
+        .filter(|c| !c.is_whitespace() && !matches!(c, '・' | '/' | '&' | '･'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Map a category name from any Booth locale to its canonical key.
+/// Unknown names are returned trimmed but otherwise unchanged.
+pub fn canonical_category(name: &str) -> String {
+    let name = name.trim();
+    let folded = fold(name);
+    CATEGORY_NAMES
+        .iter()
+        .find(|(ja, en, ko)| [ja, en, ko].iter().any(|n| fold(n) == folded))
+        .map(|(ja, _, _)| ja.to_string())
+        .unwrap_or_else(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locales_share_one_key() {
+        assert_eq!(canonical_category("3D衣装"), "3D衣装");
+        assert_eq!(canonical_category("3D Clothing"), "3D衣装");
+        assert_eq!(canonical_category("3D 의상"), "3D衣装");
+        assert_eq!(canonical_category(" 3d clothing "), "3D衣装");
+    }
+
+    #[test]
+    fn separators_and_width_are_ignored() {
+        assert_eq!(
+            canonical_category("3D Environments & Worlds"),
+            "3D環境・ワールド"
+        );
+        assert_eq!(canonical_category("3D 환경/월드"), "3D環境・ワールド");
+        assert_eq!(
+            canonical_category("3D Models（Other）"),
+            "3Dモデル（その他）"
+        );
+    }
+
+    #[test]
+    fn unknown_categories_pass_through() {
+        assert_eq!(canonical_category("  グッズ "), "グッズ");
+        assert_eq!(canonical_category("Fashion"), "Fashion");
+    }
+}
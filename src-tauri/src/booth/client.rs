@@ -0,0 +1,646 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use tauri_plugin_http::reqwest;
+
+use super::models::{
+    AdultFilter, BoothItem, BoothJsonItemDetail, Locale, SearchParams, SearchResult,
+};
+use super::parser::{self, ParsedShopPage};
+use super::scheduler::{Priority, RequestScheduler, SchedulerConfig};
+use crate::error::{AppError, AppResult};
+
+pub const BOOTH_ORIGIN: &str = "https://booth.pm";
+const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+pub const MAX_PAGE: u32 = 10_000;
+/// Attempts per request before a 429/5xx is surfaced to the caller
+const MAX_ATTEMPTS: u32 = 3;
+const VALID_SORTS: &[&str] = &["new", "popular", "price_asc", "price_desc"];
+
+/// Same character set as JS `encodeURIComponent`
+const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'!')
+    .remove(b'~')
+    .remove(b'*')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')');
+
+fn encode(s: &str) -> String {
+    utf8_percent_encode(s, COMPONENT).to_string()
+}
+
+// ── URL builder ────────────────────────────────────────
+
+fn base_url(origin: &str, locale: Locale) -> String {
+    format!("{}/{}", origin, locale.as_str())
+}
+
+/// `origin` is the main site, e.g. `https://booth.pm`.
+pub fn build_search_url(origin: &str, params: &SearchParams) -> String {
+    let page = params.page.unwrap_or(1).clamp(1, MAX_PAGE);
+    let keyword = params.keyword.trim();
+    let base = base_url(origin, params.locale.unwrap_or_default());
+
+    let mut url = match params.category.as_deref().filter(|c| !c.is_empty()) {
+        Some(category) if keyword.is_empty() => {
+            format!("{}/browse/{}?page={}", base, encode(category), page)
+        }
+        Some(category) => format!(
+            "{}/browse/{}?q={}&page={}",
+            base,
+            encode(category),
+            encode(keyword),
+            page
+        ),
+        None => format!("{}/items?q={}&page={}", base, encode(keyword), page),
+    };
+
+    if let Some(sort) = params.sort.as_deref() {
+        if VALID_SORTS.contains(&sort) {
+            url.push_str(&format!("&sort={}", sort));
+        }
+    }
+
+    if params.only_free.unwrap_or(false) {
+        url.push_str("&max_price=0");
+    } else {
+        if let Some(min) = params.price_min {
+            url.push_str(&format!("&min_price={}", min));
+        }
+        if let Some(max) = params.price_max {
+            url.push_str(&format!("&max_price={}", max));
+        }
+    }
+
+    let mut tags: Vec<&str> = Vec::new();
+    for tag in params
+        .tags
+        .iter()
+        .map(|t| t.trim())
+        .filter(|t| !t.is_empty())
+    {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    for tag in tags {
+        url.push_str(&format!("&{}={}", encode("tags[]"), encode(tag)));
+    }
+
+    if let Some(item_type) = params.item_type {
+        url.push_str(&format!("&type={}", item_type.as_str()));
+    }
+    if params.in_stock.unwrap_or(false) {
+        url.push_str("&in_stock=true");
+    }
+    if params.new_arrival.unwrap_or(false) {
+        url.push_str("&new_arrival=true");
+    }
+    match params.adult.unwrap_or_default() {
+        AdultFilter::Exclude => {}
+        AdultFilter::Include => url.push_str("&adult=include"),
+        AdultFilter::Only => url.push_str("&adult=only"),
+    }
+
+    url
+}
+
+/// Accept either a bare subdomain ("kipfel-shop") or a shop URL
+/// ("https://kipfel-shop.booth.pm/items") and return the lowercase subdomain.
+pub fn normalize_shop_subdomain(input: &str) -> AppResult<String> {
+    let s = input.trim().to_ascii_lowercase();
+    let s = s
+        .strip_prefix("https://")
+        .or_else(|| s.strip_prefix("http://"))
+        .unwrap_or(&s);
+    let s = s.split(['/', '?', '#']).next().unwrap_or_default();
+    let s = s.strip_suffix(".booth.pm").unwrap_or(s);
+
+    let valid = !s.is_empty()
+        && !s.starts_with('-')
+        && !s.ends_with('-')
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !valid {
+        return Err(AppError::Validation(format!(
+            "Invalid shop subdomain: {}",
+            input
+        )));
+    }
+    Ok(s.to_string())
+}
+
+/// Shops live on a subdomain of the main site: `https://{subdomain}.booth.pm`.
+pub fn build_shop_url(origin: &str, subdomain: &str, page: u32) -> String {
+    let shop_origin = match origin.split_once("://") {
+        Some((scheme, host)) => format!("{}://{}.{}", scheme, subdomain, host),
+        None => format!("{}.{}", subdomain, origin),
+    };
+    format!("{}/items?page={}", shop_origin, page.clamp(1, MAX_PAGE))
+}
+
+// ── Client ─────────────────────────────────────────────
+
+/// Where and how fast the client talks to Booth. Tests point it at a local server.
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// Scheme and host of the main site, without a trailing slash
+    pub origin: String,
+    pub scheduler: SchedulerConfig,
+    /// Fixed DNS answers, so shop subdomains of a test origin reach the local server
+    pub resolve: Vec<(String, SocketAddr)>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            origin: BOOTH_ORIGIN.to_string(),
+            scheduler: SchedulerConfig::default(),
+            resolve: Vec::new(),
+        }
+    }
+}
+
+pub struct BoothClient {
+    http: reqwest::Client,
+    scheduler: RequestScheduler,
+    origin: String,
+}
+
+impl BoothClient {
+    pub fn new() -> AppResult<Self> {
+        Self::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> AppResult<Self> {
+        let mut builder = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(REQUEST_TIMEOUT);
+        if !config.resolve.is_empty() {
+            // Overridden hosts are local; keep them away from any system proxy
+            builder = builder.no_proxy();
+        }
+        for (host, addr) in &config.resolve {
+            builder = builder.resolve(host, *addr);
+        }
+        let http = builder
+            .build()
+            .map_err(|e| AppError::Network(format!("Failed to build HTTP client: {}", e)))?;
+        Ok(Self {
+            http,
+            scheduler: RequestScheduler::new(config.scheduler),
+            origin: config.origin.trim_end_matches('/').to_string(),
+        })
+    }
+
+    pub async fn search(
+        &self,
+        params: &SearchParams,
+        priority: Priority,
+    ) -> AppResult<SearchResult> {
+        let url = build_search_url(&self.origin, params);
+        let html = self.fetch_text(&url, priority).await?;
+        let page = parser::parse_search_html(&html, params.locale.unwrap_or_default());
+        Ok(SearchResult {
+            items: page.items,
+            total_count: page.total_count,
+            current_page: params.page.unwrap_or(1).clamp(1, MAX_PAGE),
+            offline: false,
+            cached_at: None,
+        })
+    }
+
+    /// Fetch a single item, preferring the JSON API and falling back to the HTML page
+    /// when the JSON is missing or unreadable. Rate limits, network failures and
+    /// private items are returned as is: the HTML page would fail the same way,
+    /// and another request series would only add load while Booth throttles us.
+    pub async fn get_item(
+        &self,
+        item_id: i64,
+        locale: Locale,
+        priority: Priority,
+    ) -> AppResult<BoothItem> {
+        match self.get_item_json(item_id, locale, priority).await {
+            Ok(detail) => return Ok(detail.into_item(locale)),
+            Err(e @ (AppError::ParseError(_) | AppError::NotFound(_))) => {
+                log::warn!("JSON API failed for item {}: {}", item_id, e)
+            }
+            Err(e) => return Err(e),
+        }
+
+        let html_url = format!("{}/items/{}", base_url(&self.origin, locale), item_id);
+        let html = self.fetch_item_text(&html_url, priority).await?;
+        parser::parse_item_detail_html(&html, item_id, locale)
+            .ok_or_else(|| AppError::NotFound(format!("Item {} not found in HTML", item_id)))
+    }
+
+    /// Fetch `items/{id}.json` only, without the HTML fallback.
+    pub async fn get_item_json(
+        &self,
+        item_id: i64,
+        locale: Locale,
+        priority: Priority,
+    ) -> AppResult<BoothJsonItemDetail> {
+        let url = format!("{}/items/{}.json", base_url(&self.origin, locale), item_id);
+        let body = self.fetch_item_text(&url, priority).await?;
+        serde_json::from_str(&body).map_err(|e| {
+            AppError::ParseError(format!("Unexpected JSON for item {}: {}", item_id, e))
+        })
+    }
+
+    /// Fetch one page of a shop's item list. `subdomain` must already be normalized.
+    /// Shops live on their own subdomain, so `locale` only shapes the item URLs.
+    pub async fn get_shop_page(
+        &self,
+        subdomain: &str,
+        page: u32,
+        locale: Locale,
+        priority: Priority,
+    ) -> AppResult<ParsedShopPage> {
+        let html = self
+            .fetch_text(&build_shop_url(&self.origin, subdomain, page), priority)
+            .await?;
+        Ok(parser::parse_shop_html(&html, subdomain, locale))
+    }
+
+    async fn fetch_text(&self, url: &str, priority: Priority) -> AppResult<String> {
+        let resp = self.fetch(url, priority).await?;
+        resp.text()
+            .await
+            .map_err(|e| AppError::Network(e.to_string()))
+    }
+
+    /// Like `fetch_text`, but an item URL that redirects away from the item
+    /// (Booth sends private items to the shop or login page) is reported as
+    /// `Private`. Redirects to another URL of the same item, such as adding the
+    /// locale or leaving a shop subdomain, are followed as usual.
+    async fn fetch_item_text(&self, url: &str, priority: Priority) -> AppResult<String> {
+        let resp = self.fetch(url, priority).await?;
+        let requested = reqwest::Url::parse(url).ok();
+        if requested.is_some_and(|u| item_segment(u.path()) != item_segment(resp.url().path())) {
+            return Err(AppError::Private(url.to_string()));
+        }
+        resp.text()
+            .await
+            .map_err(|e| AppError::Network(e.to_string()))
+    }
+
+    /// GET a page through the shared scheduler, retrying 429/5xx responses
+    /// after the scheduler's cooldown.
+    async fn fetch(&self, url: &str, priority: Priority) -> AppResult<reqwest::Response> {
+        let mut attempt = 1;
+        loop {
+            self.scheduler.acquire(priority).await;
+            let resp = self
+                .http
+                .get(url)
+                .send()
+                .await
+                .map_err(|e| AppError::Network(e.to_string()))?;
+
+            let status = resp.status();
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                let delay = self.scheduler.record_failure(retry_after(resp.headers()));
+                log::warn!(
+                    "Booth.pm returned {} for {} (attempt {}/{}), cooling down {:?}",
+                    status.as_u16(),
+                    url,
+                    attempt,
+                    MAX_ATTEMPTS,
+                    delay
+                );
+                if attempt < MAX_ATTEMPTS {
+                    attempt += 1;
+                    continue;
+                }
+                if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                    // Rounded up so the UI never suggests retrying "in 0 seconds"
+                    let secs = delay.as_secs() + u64::from(delay.subsec_nanos() > 0);
+                    return Err(AppError::RateLimited {
+                        retry_after_secs: Some(secs),
+                    });
+                }
+                return Err(AppError::HttpStatus {
+                    status: status.as_u16(),
+                    url: url.to_string(),
+                });
+            }
+            self.scheduler.record_success();
+
+            if status == reqwest::StatusCode::NOT_FOUND {
+                return Err(AppError::NotFound(url.to_string()));
+            }
+            if !status.is_success() {
+                return Err(AppError::HttpStatus {
+                    status: status.as_u16(),
+                    url: url.to_string(),
+                });
+            }
+
+            return Ok(resp);
+        }
+    }
+}
+
+/// `Retry-After` in its delay-seconds form; HTTP-date values fall back to our own backoff.
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// The `items/{id}` tail of an item path (`/ja/items/1.json` → `items/1.json`).
+fn item_segment(path: &str) -> Option<&str> {
+    path.rfind("items/").map(|i| &path[i..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booth::mock_server::{
+        MockBooth, MockResponse, ITEM_HTML, ITEM_JSON, SEARCH_HTML, SHOP_HTML,
+    };
+
+    const SEARCH_URLS: &str = include_str!("../../tests/fixtures/search_urls.json");
+
+    #[derive(serde::Deserialize)]
+    struct UrlCase {
+        name: String,
+        params: SearchParams,
+        url: String,
+    }
+
+    #[test]
+    fn search_urls_match_fixtures() {
+        let cases: Vec<UrlCase> = serde_json::from_str(SEARCH_URLS).unwrap();
+        assert!(!cases.is_empty());
+        for case in cases {
+            assert_eq!(
+                build_search_url(BOOTH_ORIGIN, &case.params),
+                case.url,
+                "{}",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn shop_subdomain_accepts_bare_names_and_urls() {
+        assert_eq!(
+            normalize_shop_subdomain("kipfel-shop").unwrap(),
+            "kipfel-shop"
+        );
+        assert_eq!(
+            normalize_shop_subdomain(" https://Kipfel-Shop.booth.pm/items?page=2 ").unwrap(),
+            "kipfel-shop"
+        );
+        assert_eq!(
+            normalize_shop_subdomain("abc123.booth.pm").unwrap(),
+            "abc123"
+        );
+    }
+
+    #[test]
+    fn shop_subdomain_rejects_invalid_input() {
+        for bad in ["", "-shop", "shop-", "sh op", "evil.com/x", "a_b"] {
+            assert!(
+                normalize_shop_subdomain(bad).is_err(),
+                "{:?} should be rejected",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn shop_url_clamps_page() {
+        assert_eq!(
+            build_shop_url(BOOTH_ORIGIN, "kipfel-shop", 0),
+            "https://kipfel-shop.booth.pm/items?page=1"
+        );
+        assert_eq!(
+            build_shop_url(BOOTH_ORIGIN, "kipfel-shop", 3),
+            "https://kipfel-shop.booth.pm/items?page=3"
+        );
+    }
+
+    #[test]
+    fn shop_url_follows_a_custom_origin() {
+        assert_eq!(
+            build_shop_url("http://booth.test:8080", "kipfel-shop", 1),
+            "http://kipfel-shop.booth.test:8080/items?page=1"
+        );
+    }
+
+    // ── Against the mock server ────────────────────────
+
+    const ITEM_JSON_TARGET: &str = "booth.test/ja/items/5123456.json";
+    const ITEM_HTML_TARGET: &str = "booth.test/ja/items/5123456";
+
+    #[test]
+    fn search_fetches_and_parses_results() {
+        let server = MockBooth::start();
+        server.on(
+            "booth.test/ja/items?q=kipfel&page=2&sort=new",
+            MockResponse::html(SEARCH_HTML),
+        );
+        let params = SearchParams {
+            keyword: "kipfel".into(),
+            page: Some(2),
+            sort: Some("new".into()),
+            ..Default::default()
+        };
+
+        let result =
+            tauri::async_runtime::block_on(server.client(&[]).search(&params, Priority::User))
+                .unwrap();
+        assert_eq!(result.items.len(), 2);
+        assert_eq!(result.items[0].id, 5123456);
+        assert_eq!(result.current_page, 2);
+        assert!(!result.offline);
+    }
+
+    #[test]
+    fn item_prefers_the_json_api() {
+        let server = MockBooth::start();
+        server.on(ITEM_JSON_TARGET, MockResponse::json(ITEM_JSON));
+
+        let item = tauri::async_runtime::block_on(server.client(&[]).get_item(
+            5123456,
+            Locale::Ja,
+            Priority::User,
+        ))
+        .unwrap();
+        assert_eq!(item.name, "【キプフェル対応】ワンピース");
+        assert_eq!(item.wish_lists_count, Some(842));
+        assert_eq!(server.requests(), vec![ITEM_JSON_TARGET]);
+    }
+
+    #[test]
+    fn item_falls_back_to_html_when_json_is_missing() {
+        let server = MockBooth::start();
+        server.on(ITEM_HTML_TARGET, MockResponse::html(ITEM_HTML));
+
+        let item = tauri::async_runtime::block_on(server.client(&[]).get_item(
+            5123456,
+            Locale::Ja,
+            Priority::User,
+        ))
+        .unwrap();
+        assert_eq!(item.id, 5123456);
+        assert_eq!(item.shop_name.as_deref(), Some("Kipfel Shop"));
+        assert_eq!(server.requests(), vec![ITEM_JSON_TARGET, ITEM_HTML_TARGET]);
+    }
+
+    #[test]
+    fn missing_item_is_not_found() {
+        let server = MockBooth::start();
+        let err = tauri::async_runtime::block_on(server.client(&[]).get_item(
+            1,
+            Locale::Ja,
+            Priority::User,
+        ))
+        .unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn redirected_item_is_private() {
+        let server = MockBooth::start();
+        server
+            .on(ITEM_JSON_TARGET, MockResponse::redirect("/ja"))
+            .on(ITEM_HTML_TARGET, MockResponse::redirect("/ja"))
+            .on("booth.test/ja", MockResponse::html(SEARCH_HTML));
+
+        let err = tauri::async_runtime::block_on(server.client(&[]).get_item(
+            5123456,
+            Locale::Ja,
+            Priority::User,
+        ))
+        .unwrap_err();
+        assert!(matches!(err, AppError::Private(_)), "{:?}", err);
+        assert!(!err.retryable());
+    }
+
+    #[test]
+    fn locale_redirect_is_not_private() {
+        let server = MockBooth::start();
+        server
+            .on(
+                "booth.test/items/5123456.json",
+                MockResponse::redirect("/ja/items/5123456.json"),
+            )
+            .on(ITEM_JSON_TARGET, MockResponse::json(ITEM_JSON));
+
+        let url = format!("{}/items/5123456.json", server.origin());
+        let body = tauri::async_runtime::block_on(
+            server.client(&[]).fetch_item_text(&url, Priority::User),
+        )
+        .unwrap();
+        assert_eq!(body, ITEM_JSON);
+    }
+
+    #[test]
+    fn rate_limit_is_retried_after_retry_after() {
+        let server = MockBooth::start();
+        server
+            .on(ITEM_JSON_TARGET, MockResponse::rate_limited(1))
+            .on(ITEM_JSON_TARGET, MockResponse::json(ITEM_JSON));
+
+        let start = std::time::Instant::now();
+        let detail = tauri::async_runtime::block_on(server.client(&[]).get_item_json(
+            5123456,
+            Locale::Ja,
+            Priority::User,
+        ))
+        .unwrap();
+        assert_eq!(detail.id, 5123456);
+        assert!(start.elapsed() >= Duration::from_secs(1));
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn persistent_rate_limit_surfaces_retry_after() {
+        let server = MockBooth::start();
+        server.on(ITEM_JSON_TARGET, MockResponse::rate_limited(1));
+
+        let err = tauri::async_runtime::block_on(server.client(&[]).get_item_json(
+            5123456,
+            Locale::Ja,
+            Priority::User,
+        ))
+        .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                AppError::RateLimited {
+                    retry_after_secs: Some(1)
+                }
+            ),
+            "{:?}",
+            err
+        );
+        assert_eq!(server.requests().len(), MAX_ATTEMPTS as usize);
+    }
+
+    #[test]
+    fn server_errors_are_retried_then_reported() {
+        let server = MockBooth::start();
+        server.on(ITEM_JSON_TARGET, MockResponse::status(503));
+
+        let err = tauri::async_runtime::block_on(server.client(&[]).get_item_json(
+            5123456,
+            Locale::Ja,
+            Priority::User,
+        ))
+        .unwrap_err();
+        assert!(matches!(err, AppError::HttpStatus { status: 503, .. }));
+        assert!(err.retryable());
+        assert_eq!(server.requests().len(), MAX_ATTEMPTS as usize);
+    }
+
+    #[test]
+    fn rate_limited_item_does_not_fall_back_to_html() {
+        let server = MockBooth::start();
+        server
+            .on(ITEM_JSON_TARGET, MockResponse::rate_limited(0))
+            .on(ITEM_HTML_TARGET, MockResponse::html(ITEM_HTML));
+
+        let err = tauri::async_runtime::block_on(server.client(&[]).get_item(
+            5123456,
+            Locale::Ja,
+            Priority::User,
+        ))
+        .unwrap_err();
+        assert!(matches!(err, AppError::RateLimited { .. }), "{:?}", err);
+        assert!(server.requests().iter().all(|r| r == ITEM_JSON_TARGET));
+    }
+
+    #[test]
+    fn shop_pages_are_fetched_from_the_shop_subdomain() {
+        let server = MockBooth::start();
+        server.on(
+            "kipfel-shop.booth.test/items?page=2",
+            MockResponse::html(SHOP_HTML),
+        );
+
+        let page = tauri::async_runtime::block_on(server.client(&["kipfel-shop"]).get_shop_page(
+            "kipfel-shop",
+            2,
+            Locale::Ja,
+            Priority::User,
+        ))
+        .unwrap();
+        assert_eq!(page.shop.unwrap().name, "Kipfel Shop");
+        assert_eq!(page.items.len(), 2);
+    }
+}
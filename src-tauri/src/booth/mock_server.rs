@@ -0,0 +1,212 @@
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::client::{BoothClient, ClientConfig};
+use super::scheduler::SchedulerConfig;
+
+/// Host the mock stands in for; shops are served as `{subdomain}.booth.test`.
+pub const HOST: &str = "booth.test";
+
+pub const SEARCH_HTML: &str = include_str!("../../tests/fixtures/search_results.html");
+pub const ITEM_JSON: &str = include_str!("../../tests/fixtures/item_detail.json");
+pub const ITEM_HTML: &str = include_str!("../../tests/fixtures/item_detail.html");
+pub const SHOP_HTML: &str = include_str!("../../tests/fixtures/shop_items.html");
+
+// ── Types ──────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: String,
+}
+
+impl MockResponse {
+    pub fn html(body: &str) -> Self {
+        Self::with_type(200, "text/html; charset=utf-8", body)
+    }
+
+    pub fn json(body: &str) -> Self {
+        Self::with_type(200, "application/json", body)
+    }
+
+    pub fn status(status: u16) -> Self {
+        Self::with_type(status, "text/plain", "")
+    }
+
+    pub fn rate_limited(retry_after_secs: u64) -> Self {
+        let mut resp = Self::status(429);
+        resp.headers
+            .push(("Retry-After", retry_after_secs.to_string()));
+        resp
+    }
+
+    /// 302 to `location`, a path on the main mock host
+    pub fn redirect(location: &str) -> Self {
+        let mut resp = Self::status(302);
+        resp.headers.push(("Location", location.to_string()));
+        resp
+    }
+
+    fn with_type(status: u16, content_type: &str, body: &str) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type", content_type.to_string())],
+            body: body.to_string(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Routes {
+    /// `host + path?query` → responses served in order; the last one repeats
+    responses: HashMap<String, VecDeque<MockResponse>>,
+    /// Every request received, as `host + path?query`
+    log: Vec<String>,
+}
+
+// ── Server ─────────────────────────────────────────────
+
+/// Minimal HTTP/1.1 server that replays recorded Booth pages, so the client,
+/// scheduler and caching commands can be tested without network access.
+/// Unknown targets get a 404.
+pub struct MockBooth {
+    addr: SocketAddr,
+    routes: Arc<Mutex<Routes>>,
+}
+
+impl MockBooth {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let addr = listener.local_addr().unwrap();
+        let routes = Arc::new(Mutex::new(Routes::default()));
+
+        let shared = routes.clone();
+        // Detached: the thread ends with the test process
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let routes = shared.clone();
+                std::thread::spawn(move || handle(stream, &routes));
+            }
+        });
+        Self { addr, routes }
+    }
+
+    /// Origin to configure the client with, e.g. `http://booth.test:41234`.
+    pub fn origin(&self) -> String {
+        format!("http://{}:{}", HOST, self.addr.port())
+    }
+
+    /// Queue a response for `target`, written as `host + path?query`
+    /// (`booth.test/ja/items/1.json`, `my-shop.booth.test/items?page=1`).
+    pub fn on(&self, target: &str, response: MockResponse) -> &Self {
+        self.lock()
+            .responses
+            .entry(target.to_string())
+            .or_default()
+            .push_back(response);
+        self
+    }
+
+    /// Targets requested so far, in arrival order.
+    pub fn requests(&self) -> Vec<String> {
+        self.lock().log.clone()
+    }
+
+    /// Client pointed at this server with a fast scheduler. `shops` lists the
+    /// subdomains that should resolve here too.
+    pub fn client(&self, shops: &[&str]) -> BoothClient {
+        let mut resolve = vec![(HOST.to_string(), self.addr)];
+        for shop in shops {
+            resolve.push((format!("{}.{}", shop, HOST), self.addr));
+        }
+        BoothClient::with_config(ClientConfig {
+            origin: self.origin(),
+            scheduler: SchedulerConfig {
+                min_interval: Duration::ZERO,
+                background_interval: Duration::ZERO,
+                base_backoff: Duration::from_millis(10),
+                max_backoff: Duration::from_millis(50),
+            },
+            resolve,
+        })
+        .expect("build mock client")
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Routes> {
+        self.routes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn handle(stream: TcpStream, routes: &Mutex<Routes>) {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let path = request_line
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or("/")
+        .to_string();
+
+    let mut host = String::new();
+    loop {
+        let mut line = String::new();
+        match reader.read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) if line.trim().is_empty() => break,
+            Ok(_) => {
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("host") {
+                        let value = value.trim();
+                        host = value.split(':').next().unwrap_or(value).to_string();
+                    }
+                }
+            }
+        }
+    }
+
+    let target = format!("{}{}", host, path);
+    let response = {
+        let mut routes = routes.lock().unwrap_or_else(|e| e.into_inner());
+        routes.log.push(target.clone());
+        match routes.responses.get_mut(&target) {
+            Some(queue) if queue.len() > 1 => queue.pop_front(),
+            Some(queue) => queue.front().cloned(),
+            None => None,
+        }
+    }
+    .unwrap_or_else(|| MockResponse::status(404));
+
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        reason(response.status),
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+
+    let mut stream = &stream;
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(response.body.as_bytes());
+    let _ = stream.flush();
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        302 => "Found",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
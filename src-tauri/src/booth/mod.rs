@@ -1 +1,7 @@
+pub mod category;
+pub mod client;
+#[cfg(test)]
+pub mod mock_server;
 pub mod models;
+pub mod parser;
+pub mod scheduler;
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::category::canonical_category;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BoothItem {
     pub id: i64,
@@ -13,4 +15,437 @@ pub struct BoothItem {
     pub tags: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wish_lists_count: Option<i64>,
+    /// Only known for items fetched through the JSON API; search cards leave it empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variations: Vec<Variation>,
+    /// Booth site locale the item was fetched from
+    #[serde(default)]
+    pub locale: Locale,
+}
+
+/// Booth site locale, the first path segment of `booth.pm/{locale}/...`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Locale {
+    #[default]
+    Ja,
+    En,
+    Ko,
+}
+
+impl Locale {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Locale::Ja => "ja",
+            Locale::En => "en",
+            Locale::Ko => "ko",
+        }
+    }
+
+    /// Inverse of `as_str`; unknown values fall back to the default.
+    pub fn from_code(code: &str) -> Locale {
+        match code {
+            "en" => Locale::En,
+            "ko" => Locale::Ko,
+            _ => Locale::Ja,
+        }
+    }
+}
+
+pub fn item_url(locale: Locale, item_id: i64) -> String {
+    format!("https://booth.pm/{}/items/{}", locale.as_str(), item_id)
+}
+
+/// Size segment of the search card images, which favorites store as thumbnails
+const CARD_IMAGE_SIZE: &str = "300x300_a2_g5";
+
+/// The search card variant (`/c/300x300_a2_g5/…`) of a Booth image URL in any
+/// size, so thumbnails from item details match those saved from search
+/// results. Other URLs are returned unchanged.
+pub fn card_thumbnail(url: &str) -> String {
+    let Some(rest) = url.strip_prefix("https://") else {
+        return url.to_string();
+    };
+    let Some((host, path)) = rest.split_once('/') else {
+        return url.to_string();
+    };
+    if !host.ends_with("pximg.net") {
+        return url.to_string();
+    }
+    // Resized variants live under `/c/{size}/`; originals have no prefix
+    let path = match path.strip_prefix("c/").and_then(|p| p.split_once('/')) {
+        Some((_, image)) => image,
+        None => path,
+    };
+    format!("https://{}/c/{}/{}", host, CARD_IMAGE_SIZE, path)
+}
+
+/// Canonical category key for a scraped/API category name; see `category.rs`.
+pub fn category_key(name: Option<String>) -> Option<String> {
+    name.map(|n| canonical_category(&n))
+        .filter(|n| !n.is_empty())
+}
+
+/// One purchasable option of an item (base version, full set, support tier, goods...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Variation {
+    pub name: Option<String>,
+    pub price: i64,
+    pub kind: VariationKind,
+    pub stock: StockStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VariationKind {
+    Digital,
+    Physical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StockStatus {
+    Available,
+    Low,
+    SoldOut,
+}
+
+impl VariationKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            VariationKind::Digital => "digital",
+            VariationKind::Physical => "physical",
+        }
+    }
+
+    pub fn from_code(code: &str) -> VariationKind {
+        match code {
+            "physical" => VariationKind::Physical,
+            _ => VariationKind::Digital,
+        }
+    }
+}
+
+impl StockStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StockStatus::Available => "available",
+            StockStatus::Low => "low",
+            StockStatus::SoldOut => "sold_out",
+        }
+    }
+
+    pub fn from_code(code: &str) -> StockStatus {
+        match code {
+            "low" => StockStatus::Low,
+            "sold_out" => StockStatus::SoldOut,
+            _ => StockStatus::Available,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SearchParams {
+    pub keyword: String,
+    pub page: Option<u32>,
+    pub category: Option<String>,
+    pub sort: Option<String>,
+    pub only_free: Option<bool>,
+    pub price_min: Option<i64>,
+    pub price_max: Option<i64>,
+    /// Items must carry every one of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub item_type: Option<ItemType>,
+    pub in_stock: Option<bool>,
+    /// Restrict to Booth's "new arrivals" (recently listed items)
+    pub new_arrival: Option<bool>,
+    pub adult: Option<AdultFilter>,
+    pub locale: Option<Locale>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemType {
+    Digital,
+    Physical,
+}
+
+/// Booth hides adult items unless asked otherwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdultFilter {
+    #[default]
+    Exclude,
+    Include,
+    Only,
+}
+
+impl ItemType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ItemType::Digital => "digital",
+            ItemType::Physical => "physical",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    pub items: Vec<BoothItem>,
+    pub total_count: Option<i64>,
+    pub current_page: u32,
+    /// Served from `cached_items` because Booth could not be reached
+    pub offline: bool,
+    /// For offline results: when the oldest returned item was cached
+    pub cached_at: Option<String>,
+}
+
+/// A creator's storefront at `{subdomain}.booth.pm`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShopInfo {
+    pub subdomain: String,
+    pub name: String,
+    pub icon_url: Option<String>,
+    pub description: Option<String>,
+    pub item_count: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ShopItemsResult {
+    pub shop: ShopInfo,
+    pub items: Vec<BoothItem>,
+    pub current_page: u32,
+}
+
+// ── JSON API (`items/{id}.json`) ───────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct BoothJsonItemDetail {
+    pub id: i64,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_price")]
+    pub price: i64,
+    pub url: Option<String>,
+    #[serde(default)]
+    pub images: Vec<BoothJsonImage>,
+    #[serde(default)]
+    pub tags: Vec<BoothJsonNamed>,
+    pub category: Option<BoothJsonNamed>,
+    pub shop: Option<BoothJsonShop>,
+    pub wish_lists_count: Option<i64>,
+    #[serde(default)]
+    pub variations: Vec<BoothJsonVariation>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BoothJsonVariation {
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_price")]
+    pub price: i64,
+    /// "digital" for downloads; "direct", "factory" etc. are shipped goods
+    #[serde(rename = "type")]
+    pub variation_type: Option<String>,
+    pub status: Option<String>,
+    #[serde(default)]
+    pub is_empty_stock: bool,
+    pub small_stock: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BoothJsonImage {
+    pub original: Option<String>,
+    pub resized: Option<String>,
+}
+
+/// Tags and categories are both `{ "name": ..., "url": ... }` objects.
+#[derive(Debug, Deserialize)]
+pub struct BoothJsonNamed {
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BoothJsonShop {
+    pub name: Option<String>,
+}
+
+/// Booth sends `price` as a number on some endpoints and as a display string
+/// like "¥ 1,500" on others.
+fn deserialize_price<'de, D>(deserializer: D) -> Result<i64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(match value {
+        Some(serde_json::Value::Number(n)) => n.as_i64().unwrap_or(0),
+        Some(serde_json::Value::String(s)) => parse_price_text(&s),
+        _ => 0,
+    })
+}
+
+/// Parse the first number in a display price, ignoring currency marks and
+/// thousands separators.
+pub fn parse_price_text(text: &str) -> i64 {
+    text.chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(|c| c.is_ascii_digit() || *c == ',')
+        .filter(char::is_ascii_digit)
+        .collect::<String>()
+        .parse()
+        .unwrap_or(0)
+}
+
+fn non_empty(s: Option<String>) -> Option<String> {
+    s.map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+impl From<BoothJsonVariation> for Variation {
+    fn from(v: BoothJsonVariation) -> Self {
+        let kind = match v.variation_type.as_deref() {
+            Some("digital") | None => VariationKind::Digital,
+            Some(_) => VariationKind::Physical,
+        };
+        let stock = if v.is_empty_stock || v.status.as_deref() == Some("sold_out") {
+            StockStatus::SoldOut
+        } else if v.small_stock.is_some() {
+            StockStatus::Low
+        } else {
+            StockStatus::Available
+        };
+        Variation {
+            name: non_empty(v.name),
+            price: v.price,
+            kind,
+            stock,
+        }
+    }
+}
+
+impl BoothJsonItemDetail {
+    /// `locale` is the site the JSON was requested from.
+    pub fn into_item(self, locale: Locale) -> BoothItem {
+        let images = self
+            .images
+            .into_iter()
+            .filter_map(|img| non_empty(img.original).or_else(|| non_empty(img.resized)))
+            .collect();
+        let tags = self
+            .tags
+            .into_iter()
+            .filter_map(|t| non_empty(t.name))
+            .collect();
+
+        BoothItem {
+            id: self.id,
+            name: self.name.unwrap_or_default(),
+            description: non_empty(self.description),
+            price: self.price,
+            category_name: category_key(self.category.and_then(|c| non_empty(c.name))),
+            shop_name: self.shop.and_then(|s| non_empty(s.name)),
+            url: non_empty(self.url).unwrap_or_else(|| item_url(locale, self.id)),
+            images,
+            tags,
+            wish_lists_count: self.wish_lists_count,
+            variations: self.variations.into_iter().map(Variation::from).collect(),
+            locale,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DETAIL_JSON: &str = include_str!("../../tests/fixtures/item_detail.json");
+
+    #[test]
+    fn json_detail_maps_to_booth_item() {
+        let detail: BoothJsonItemDetail = serde_json::from_str(DETAIL_JSON).unwrap();
+        let item = detail.into_item(Locale::Ja);
+        assert_eq!(item.id, 5123456);
+        assert_eq!(item.price, 1500);
+        assert_eq!(item.category_name.as_deref(), Some("3D衣装"));
+        assert_eq!(item.shop_name.as_deref(), Some("Kipfel Shop"));
+        assert_eq!(item.tags, vec!["キプフェル", "VRChat"]);
+        assert_eq!(item.wish_lists_count, Some(842));
+        // Second image has no original and falls back to the resized URL
+        assert_eq!(item.images.len(), 2);
+        assert!(item.images[1].contains("72x72"));
+
+        assert_eq!(item.variations.len(), 2);
+        assert_eq!(item.variations[0].name.as_deref(), Some("通常版"));
+        assert_eq!(item.variations[1].price, 3000);
+        assert_eq!(item.variations[1].kind, VariationKind::Digital);
+    }
+
+    #[test]
+    fn card_thumbnails_share_the_image_path() {
+        let card = "https://booth.pximg.net/c/300x300_a2_g5/abc/i/1/aaa_base_resized.jpg";
+        assert_eq!(card_thumbnail(card), card);
+        assert_eq!(
+            card_thumbnail("https://booth.pximg.net/abc/i/1/aaa_base_resized.jpg"),
+            card
+        );
+        assert_eq!(
+            card_thumbnail("https://booth.pximg.net/c/72x72_a2_g5/abc/i/1/aaa_base_resized.jpg"),
+            card
+        );
+        assert_eq!(
+            card_thumbnail("https://example.com/a.jpg"),
+            "https://example.com/a.jpg"
+        );
+    }
+
+    #[test]
+    fn variation_kind_and_stock_from_json() {
+        let parse =
+            |json: &str| Variation::from(serde_json::from_str::<BoothJsonVariation>(json).unwrap());
+
+        let goods = parse(
+            r#"{"name": "アクスタ", "price": 1200, "type": "direct", "status": "on_sale", "is_empty_stock": false, "small_stock": 3}"#,
+        );
+        assert_eq!(goods.kind, VariationKind::Physical);
+        assert_eq!(goods.stock, StockStatus::Low);
+
+        let sold_out =
+            parse(r#"{"name": null, "price": "¥ 800", "type": "factory", "status": "sold_out"}"#);
+        assert_eq!(sold_out.price, 800);
+        assert_eq!(sold_out.stock, StockStatus::SoldOut);
+        assert!(sold_out.name.is_none());
+    }
+
+    #[test]
+    fn json_price_accepts_number_string_or_null() {
+        let parse = |price: &str| {
+            let json = format!(r#"{{"id": 1, "price": {}}}"#, price);
+            serde_json::from_str::<BoothJsonItemDetail>(&json)
+                .unwrap()
+                .price
+        };
+        assert_eq!(parse("1200"), 1200);
+        assert_eq!(parse(r#""¥ 3,000""#), 3000);
+        assert_eq!(parse(r#""1,000 ~ 5,000""#), 1000);
+        assert_eq!(parse("null"), 0);
+    }
+
+    #[test]
+    fn json_detail_keeps_locale_and_canonical_category() {
+        let detail: BoothJsonItemDetail =
+            serde_json::from_str(r#"{"id": 7, "category": {"name": "3D Clothing"}}"#).unwrap();
+        let item = detail.into_item(Locale::En);
+        assert_eq!(item.locale, Locale::En);
+        assert_eq!(item.url, "https://booth.pm/en/items/7");
+        assert_eq!(item.category_name.as_deref(), Some("3D衣装"));
+    }
+
+    #[test]
+    fn json_detail_with_missing_fields_uses_defaults() {
+        let detail: BoothJsonItemDetail = serde_json::from_str(r#"{"id": 42}"#).unwrap();
+        let item = detail.into_item(Locale::Ja);
+        assert_eq!(item.name, "");
+        assert_eq!(item.url, "https://booth.pm/ja/items/42");
+        assert!(item.images.is_empty());
+        assert!(item.category_name.is_none());
+    }
 }
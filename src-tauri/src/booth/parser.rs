@@ -0,0 +1,587 @@
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+
+use super::models::{category_key, item_url, parse_price_text, BoothItem, Locale, ShopInfo};
+
+pub struct ParsedSearchPage {
+    pub items: Vec<BoothItem>,
+    pub total_count: Option<i64>,
+}
+
+// Booth has shipped several card layouts over the years; each list is tried
+// in order and the first selector that matches wins.
+
+const CARD_SELECTORS: &[&str] = &[
+    "li.item-card[data-product-id]",
+    "[data-product-id].item-card",
+    ".item-card[data-product-id]",
+];
+const IMAGE_SELECTOR: &str =
+    "a.js-thumbnail-image[data-original], img[data-original], img.js-thumbnail-image";
+const SHOP_SELECTORS: &[&str] = &[".item-card__shop-name", ".shop-name", "[data-shop-name]"];
+const CATEGORY_SELECTORS: &[&str] = &[
+    ".item-card__category-anchor",
+    ".item-card__category a",
+    "[data-category]",
+];
+const TITLE_SELECTORS: &[&str] = &[
+    ".item-card__title-anchor--multiline",
+    ".item-card__title a",
+    "a[data-product-name]",
+    ".item-card__title",
+];
+const TOTAL_COUNT_SELECTORS: &[&str] = &[
+    ".u-tpg-caption1",
+    ".search-result-count",
+    ".u-tpg-body2",
+    "title",
+];
+
+// ── Helpers ────────────────────────────────────────────
+
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).expect("static selector must be valid")
+}
+
+fn text_of(el: ElementRef) -> String {
+    el.text().collect::<String>().trim().to_string()
+}
+
+fn query_first<'a>(el: ElementRef<'a>, selectors: &[&str]) -> Option<ElementRef<'a>> {
+    selectors
+        .iter()
+        .find_map(|css| el.select(&selector(css)).next())
+}
+
+/// Prefer the given data attribute, fall back to the element's text.
+fn attr_or_text(el: ElementRef, attr: &str) -> Option<String> {
+    el.value()
+        .attr(attr)
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .or_else(|| Some(text_of(el)).filter(|s| !s.is_empty()))
+}
+
+// ── Search results ─────────────────────────────────────
+
+pub fn parse_search_html(html: &str, locale: Locale) -> ParsedSearchPage {
+    let doc = Html::parse_document(html);
+    let root = doc.root_element();
+
+    let items = CARD_SELECTORS
+        .iter()
+        .map(|css| root.select(&selector(css)).collect::<Vec<_>>())
+        .find(|cards| !cards.is_empty())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|card| parse_item_card(card, locale))
+        .collect();
+
+    ParsedSearchPage {
+        items,
+        total_count: parse_total_count(root),
+    }
+}
+
+fn parse_item_card(card: ElementRef, locale: Locale) -> Option<BoothItem> {
+    let attrs = card.value();
+    let id: i64 = attrs.attr("data-product-id")?.trim().parse().ok()?;
+    if id <= 0 {
+        return None;
+    }
+
+    let price = attrs
+        .attr("data-product-price")
+        .and_then(|p| p.trim().parse().ok())
+        .unwrap_or(0);
+
+    let mut images: Vec<String> = Vec::new();
+    for img in card.select(&selector(IMAGE_SELECTOR)) {
+        let v = img.value();
+        let src = v
+            .attr("data-original")
+            .or_else(|| v.attr("data-src"))
+            .or_else(|| v.attr("src"));
+        if let Some(src) = src.filter(|s| !s.is_empty()) {
+            if !images.iter().any(|i| i == src) {
+                images.push(src.to_string());
+            }
+        }
+    }
+
+    let shop_name =
+        query_first(card, SHOP_SELECTORS).and_then(|el| attr_or_text(el, "data-shop-name"));
+    let category_name = category_key(
+        query_first(card, CATEGORY_SELECTORS).and_then(|el| attr_or_text(el, "data-category")),
+    );
+
+    let name = attrs
+        .attr("data-product-name")
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .or_else(|| {
+            query_first(card, TITLE_SELECTORS).and_then(|el| attr_or_text(el, "data-product-name"))
+        })?;
+
+    Some(BoothItem {
+        id,
+        name,
+        description: None,
+        price,
+        category_name,
+        shop_name,
+        url: item_url(locale, id),
+        images,
+        tags: Vec::new(),
+        wish_lists_count: None,
+        variations: Vec::new(),
+        locale,
+    })
+}
+
+fn parse_total_count(root: ElementRef) -> Option<i64> {
+    TOTAL_COUNT_SELECTORS.iter().find_map(|css| {
+        root.select(&selector(css))
+            .find_map(|el| extract_count(&text_of(el)))
+    })
+}
+
+/// Extract a total from text like "1,234件" or "567点"
+fn extract_count(text: &str) -> Option<i64> {
+    let re = Regex::new(r"([\d,]+)\s*[件点]").expect("static regex must be valid");
+    let caps = re.captures(text)?;
+    let n: i64 = caps[1].replace(',', "").parse().ok()?;
+    (n > 0).then_some(n)
+}
+
+// ── Item detail ────────────────────────────────────────
+
+const DETAIL_TITLE_SELECTORS: &[&str] = &[
+    "[data-product-name]",
+    ".u-tpg-title2",
+    "h2.u-tpg-title2",
+    ".item-name h1",
+    "h1",
+];
+const DETAIL_PRICE_SELECTORS: &[&str] = &[
+    ".item-price .u-tpg-body1",
+    ".price",
+    ".u-tpg-title2-price",
+    "[data-product-price]",
+];
+const DETAIL_DESCRIPTION_SELECTORS: &[&str] = &[
+    ".u-mb-400 .u-tpg-body1",
+    ".item-description",
+    ".description",
+];
+const DETAIL_IMAGE_SELECTOR: &str =
+    ".item-gallery img, .slick-slide img, .market-item-detail-item-image img, .js-thumbnail img";
+const DETAIL_SHOP_SELECTORS: &[&str] = &[
+    ".shop-name",
+    ".shop-name-mini a",
+    ".u-d-ib a",
+    "[data-shop-name]",
+];
+const DETAIL_TAG_SELECTOR: &str = ".item-tag a, a.tag, .item-info-tag a, .tag-list a";
+const DETAIL_CATEGORY_SELECTORS: &[&str] =
+    &[".item-category a", ".category-name a", "[data-category]"];
+const DETAIL_WISH_SELECTORS: &[&str] = &[
+    ".wish-list-count",
+    "[data-wish-count]",
+    ".u-flex-wrap .u-tpg-body1",
+];
+
+/// Fallback for when `items/{id}.json` is unavailable: scrape the item page.
+pub fn parse_item_detail_html(html: &str, item_id: i64, locale: Locale) -> Option<BoothItem> {
+    let doc = Html::parse_document(html);
+    let root = doc.root_element();
+
+    let name = query_first(root, DETAIL_TITLE_SELECTORS)
+        .and_then(|el| attr_or_text(el, "data-product-name"))?;
+
+    let price = query_first(root, DETAIL_PRICE_SELECTORS)
+        .map(|el| match el.value().attr("data-product-price") {
+            Some(attr) => attr.trim().parse().unwrap_or(0),
+            None => parse_price_text(&text_of(el)),
+        })
+        .unwrap_or(0);
+
+    let description = query_first(root, DETAIL_DESCRIPTION_SELECTORS)
+        .map(text_of)
+        .filter(|s| !s.is_empty());
+
+    let mut images: Vec<String> = Vec::new();
+    for img in root.select(&selector(DETAIL_IMAGE_SELECTOR)) {
+        let v = img.value();
+        let src = v
+            .attr("data-origin")
+            .or_else(|| v.attr("data-original"))
+            .or_else(|| v.attr("data-src"))
+            .or_else(|| v.attr("src"));
+        if let Some(src) = src.filter(|s| !s.is_empty()) {
+            if !images.iter().any(|i| i == src) {
+                images.push(src.to_string());
+            }
+        }
+    }
+
+    let shop_name =
+        query_first(root, DETAIL_SHOP_SELECTORS).and_then(|el| attr_or_text(el, "data-shop-name"));
+
+    let mut tags: Vec<String> = Vec::new();
+    for el in root.select(&selector(DETAIL_TAG_SELECTOR)) {
+        let t = text_of(el);
+        if !t.is_empty() && !tags.contains(&t) {
+            tags.push(t);
+        }
+    }
+
+    let category_name = category_key(
+        query_first(root, DETAIL_CATEGORY_SELECTORS)
+            .and_then(|el| attr_or_text(el, "data-category")),
+    );
+
+    let wish_lists_count = query_first(root, DETAIL_WISH_SELECTORS).and_then(|el| {
+        match el.value().attr("data-wish-count") {
+            Some(attr) => attr.trim().parse().ok(),
+            None => {
+                let re = Regex::new(r"[\d,]+").expect("static regex must be valid");
+                re.find(&text_of(el))
+                    .and_then(|m| m.as_str().replace(',', "").parse().ok())
+            }
+        }
+    });
+
+    Some(BoothItem {
+        id: item_id,
+        name,
+        description,
+        price,
+        category_name,
+        shop_name,
+        url: item_url(locale, item_id),
+        images,
+        tags,
+        wish_lists_count,
+        variations: Vec::new(),
+        locale,
+    })
+}
+
+// ── Shop pages ─────────────────────────────────────────
+
+pub struct ParsedShopPage {
+    /// `None` when the shop header could not be found
+    pub shop: Option<ShopInfo>,
+    pub items: Vec<BoothItem>,
+}
+
+const SHOP_CARD_SELECTORS: &[&str] = &[
+    "li.item[data-product-id]",
+    ".shop-item-card[data-product-id]",
+    "[data-product-id]",
+];
+const SHOP_CARD_TITLE_SELECTORS: &[&str] = &[
+    ".item-name a",
+    ".item-card__title a",
+    ".item-card__title",
+    ".item-name",
+];
+const SHOP_CARD_PRICE_SELECTORS: &[&str] = &[".price", ".item-card__price"];
+const SHOP_NAME_SELECTORS: &[&str] = &[
+    ".home-link-container__nickname a",
+    ".shop-name",
+    ".shop-header h1",
+];
+const SHOP_ICON_SELECTORS: &[&str] = &[".avatar-image", ".shop-header img.avatar"];
+const SHOP_DESCRIPTION_SELECTORS: &[&str] = &[
+    ".booth-description .autolink",
+    ".booth-description",
+    ".shop-description",
+];
+const SHOP_ITEM_COUNT_SELECTORS: &[&str] = &[".shop-item-count", ".item-count"];
+
+fn meta_content(root: ElementRef, property: &str) -> Option<String> {
+    let css = format!("meta[property=\"{}\"]", property);
+    root.select(&selector(&css))
+        .next()
+        .and_then(|el| el.value().attr("content"))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Icons are either an `<img>` or a div with an inline `background-image`.
+fn icon_url(el: ElementRef) -> Option<String> {
+    if let Some(src) = el.value().attr("src").filter(|s| !s.is_empty()) {
+        return Some(src.to_string());
+    }
+    let style = el.value().attr("style")?;
+    let re = Regex::new(r#"url\(['"]?([^'")]+)['"]?\)"#).expect("static regex must be valid");
+    re.captures(style).map(|caps| caps[1].to_string())
+}
+
+/// Parse `{subdomain}.booth.pm/items`: the shop header plus its item cards.
+pub fn parse_shop_html(html: &str, subdomain: &str, locale: Locale) -> ParsedShopPage {
+    let doc = Html::parse_document(html);
+    let root = doc.root_element();
+
+    let shop = parse_shop_header(root, subdomain);
+    let shop_name = shop.as_ref().map(|s| s.name.clone());
+
+    let items = SHOP_CARD_SELECTORS
+        .iter()
+        .map(|css| root.select(&selector(css)).collect::<Vec<_>>())
+        .find(|cards| !cards.is_empty())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|card| parse_shop_card(card, shop_name.clone(), locale))
+        .collect();
+
+    ParsedShopPage { shop, items }
+}
+
+fn parse_shop_header(root: ElementRef, subdomain: &str) -> Option<ShopInfo> {
+    let name = query_first(root, SHOP_NAME_SELECTORS)
+        .map(text_of)
+        .filter(|s| !s.is_empty())
+        .or_else(|| {
+            meta_content(root, "og:title")
+                .map(|t| t.trim_end_matches(" - BOOTH").trim().to_string())
+        })?;
+
+    let icon_url = query_first(root, SHOP_ICON_SELECTORS)
+        .and_then(icon_url)
+        .or_else(|| meta_content(root, "og:image"));
+    let description = query_first(root, SHOP_DESCRIPTION_SELECTORS)
+        .map(text_of)
+        .filter(|s| !s.is_empty())
+        .or_else(|| meta_content(root, "og:description"));
+    let item_count = SHOP_ITEM_COUNT_SELECTORS.iter().find_map(|css| {
+        root.select(&selector(css))
+            .find_map(|el| extract_count(&text_of(el)))
+    });
+
+    Some(ShopInfo {
+        subdomain: subdomain.to_string(),
+        name,
+        icon_url,
+        description,
+        item_count,
+    })
+}
+
+fn parse_shop_card(
+    card: ElementRef,
+    shop_name: Option<String>,
+    locale: Locale,
+) -> Option<BoothItem> {
+    let attrs = card.value();
+    let id: i64 = attrs.attr("data-product-id")?.trim().parse().ok()?;
+    if id <= 0 {
+        return None;
+    }
+
+    let name = attrs
+        .attr("data-product-name")
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .or_else(|| {
+            query_first(card, SHOP_CARD_TITLE_SELECTORS)
+                .map(text_of)
+                .filter(|s| !s.is_empty())
+        })?;
+
+    let price = match attrs.attr("data-product-price") {
+        Some(attr) => attr.trim().parse().unwrap_or(0),
+        None => query_first(card, SHOP_CARD_PRICE_SELECTORS)
+            .map(|el| parse_price_text(&text_of(el)))
+            .unwrap_or(0),
+    };
+
+    let mut images: Vec<String> = Vec::new();
+    for img in card.select(&selector(IMAGE_SELECTOR)) {
+        let v = img.value();
+        let src = v.attr("data-original").or_else(|| v.attr("src"));
+        if let Some(src) = src.filter(|s| !s.is_empty() && !s.starts_with("data:")) {
+            if !images.iter().any(|i| i == src) {
+                images.push(src.to_string());
+            }
+        }
+    }
+
+    Some(BoothItem {
+        id,
+        name,
+        description: None,
+        price,
+        category_name: None,
+        shop_name,
+        url: item_url(locale, id),
+        images,
+        tags: Vec::new(),
+        wish_lists_count: None,
+        variations: Vec::new(),
+        locale,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEARCH_HTML: &str = include_str!("../../tests/fixtures/search_results.html");
+    const LEGACY_HTML: &str = include_str!("../../tests/fixtures/search_results_legacy.html");
+    const EMPTY_HTML: &str = include_str!("../../tests/fixtures/search_results_empty.html");
+    const DETAIL_HTML: &str = include_str!("../../tests/fixtures/item_detail.html");
+    const SHOP_HTML: &str = include_str!("../../tests/fixtures/shop_items.html");
+
+    #[test]
+    fn parses_current_item_cards() {
+        let page = parse_search_html(SEARCH_HTML, Locale::Ja);
+        assert_eq!(page.items.len(), 2);
+
+        let item = &page.items[0];
+        assert_eq!(item.id, 5123456);
+        assert_eq!(item.name, "【キプフェル対応】ワンピース");
+        assert_eq!(item.price, 1500);
+        assert_eq!(item.category_name.as_deref(), Some("3D衣装"));
+        assert_eq!(item.shop_name.as_deref(), Some("Kipfel Shop"));
+        assert_eq!(item.url, "https://booth.pm/ja/items/5123456");
+        assert_eq!(item.images.len(), 2);
+        assert!(item.images[0].ends_with("/5123456/aaa_base_resized.jpg"));
+        assert!(item.tags.is_empty());
+        assert!(item.wish_lists_count.is_none());
+
+        let free = &page.items[1];
+        assert_eq!(free.id, 4000001);
+        assert_eq!(free.price, 0);
+        assert_eq!(free.shop_name.as_deref(), Some("フリーストア"));
+    }
+
+    #[test]
+    fn item_urls_follow_the_requested_locale() {
+        let page = parse_search_html(SEARCH_HTML, Locale::Ko);
+        assert_eq!(page.items[0].url, "https://booth.pm/ko/items/5123456");
+        assert_eq!(page.items[0].locale, Locale::Ko);
+    }
+
+    #[test]
+    fn parses_total_count_with_thousands_separator() {
+        let page = parse_search_html(SEARCH_HTML, Locale::Ja);
+        assert_eq!(page.total_count, Some(1234));
+    }
+
+    #[test]
+    fn falls_back_to_legacy_card_markup() {
+        let page = parse_search_html(LEGACY_HTML, Locale::Ja);
+        // The third card has no usable name and is dropped
+        assert_eq!(page.items.len(), 2);
+
+        let by_title = &page.items[0];
+        assert_eq!(by_title.id, 3100200);
+        assert_eq!(by_title.name, "パーカー【複数アバター対応】");
+        assert_eq!(by_title.price, 2800);
+        assert_eq!(by_title.category_name.as_deref(), Some("3D衣装"));
+        assert_eq!(by_title.shop_name.as_deref(), Some("Legacy Atelier"));
+        assert_eq!(
+            by_title.images,
+            vec!["https://booth.pximg.net/c/300x300_a2_g5/ghi/i/3100200/ddd_base_resized.jpg"]
+        );
+
+        let by_attr = &page.items[1];
+        assert_eq!(by_attr.name, "属性から取る名前");
+        assert_eq!(by_attr.price, 0);
+        assert_eq!(by_attr.category_name.as_deref(), Some("3Dモデル"));
+        assert_eq!(by_attr.shop_name.as_deref(), Some("Attr Shop"));
+    }
+
+    #[test]
+    fn falls_back_to_title_for_total_count() {
+        let page = parse_search_html(LEGACY_HTML, Locale::Ja);
+        assert_eq!(page.total_count, Some(567));
+    }
+
+    #[test]
+    fn empty_results_page() {
+        let page = parse_search_html(EMPTY_HTML, Locale::Ja);
+        assert!(page.items.is_empty());
+        assert_eq!(page.total_count, None);
+    }
+
+    #[test]
+    fn extract_count_rejects_zero_and_missing() {
+        assert_eq!(extract_count("12,345 件"), Some(12345));
+        assert_eq!(extract_count("0件"), None);
+        assert_eq!(extract_count("BOOTH"), None);
+    }
+
+    #[test]
+    fn parses_item_detail_page() {
+        let item =
+            parse_item_detail_html(DETAIL_HTML, 5123456, Locale::Ja).expect("detail should parse");
+        assert_eq!(item.id, 5123456);
+        assert_eq!(item.name, "【キプフェル対応】ワンピース");
+        assert_eq!(item.price, 1500);
+        assert_eq!(item.category_name.as_deref(), Some("3D衣装"));
+        assert_eq!(item.shop_name.as_deref(), Some("Kipfel Shop"));
+        assert_eq!(
+            item.description.as_deref(),
+            Some("キプフェル向けのワンピースです。")
+        );
+        assert_eq!(item.images.len(), 2);
+        assert_eq!(item.tags, vec!["キプフェル", "VRChat"]);
+        assert_eq!(item.wish_lists_count, Some(842));
+    }
+
+    #[test]
+    fn item_detail_without_title_is_rejected() {
+        assert!(parse_item_detail_html(EMPTY_HTML, 1, Locale::Ja).is_none());
+    }
+
+    #[test]
+    fn parses_shop_header_and_items() {
+        let page = parse_shop_html(SHOP_HTML, "kipfel-shop", Locale::Ja);
+        let shop = page.shop.expect("shop header should parse");
+        assert_eq!(shop.subdomain, "kipfel-shop");
+        assert_eq!(shop.name, "Kipfel Shop");
+        assert_eq!(
+            shop.icon_url.as_deref(),
+            Some("https://booth.pximg.net/c/128x128/users/123456/icon_image/icon.png")
+        );
+        assert_eq!(
+            shop.description.as_deref(),
+            Some("キプフェル向けの衣装を作っています。")
+        );
+        assert_eq!(shop.item_count, Some(42));
+
+        // The card with a non-numeric id is dropped
+        assert_eq!(page.items.len(), 2);
+        let item = &page.items[0];
+        assert_eq!(item.id, 5123456);
+        assert_eq!(item.name, "【キプフェル対応】ワンピース");
+        assert_eq!(item.price, 1500);
+        assert_eq!(item.shop_name.as_deref(), Some("Kipfel Shop"));
+        assert_eq!(item.url, "https://booth.pm/ja/items/5123456");
+        // Lazy-load placeholders are not real images
+        assert_eq!(item.images.len(), 1);
+        assert_eq!(page.items[1].price, 0);
+    }
+
+    #[test]
+    fn shop_header_falls_back_to_og_meta() {
+        let html = r#"<html><head>
+            <meta property="og:title" content="Meta Shop - BOOTH">
+            <meta property="og:image" content="https://example.com/icon.png">
+            </head><body></body></html>"#;
+        let page = parse_shop_html(html, "meta-shop", Locale::Ja);
+        let shop = page.shop.expect("og:title should be enough");
+        assert_eq!(shop.name, "Meta Shop");
+        assert_eq!(
+            shop.icon_url.as_deref(),
+            Some("https://example.com/icon.png")
+        );
+        assert!(shop.description.is_none());
+        assert!(shop.item_count.is_none());
+        assert!(page.items.is_empty());
+    }
+}
@@ -0,0 +1,286 @@
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use tokio::sync::Notify;
+
+/// Ceiling for a server's `Retry-After`; the cooldown is shared with user
+/// searches, so one absurd value must not stall the app for hours.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(300);
+
+/// Who is asking. Higher priorities always go first; lower ones only run when
+/// nothing more important is waiting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Priority {
+    /// Periodic jobs such as popular avatar updates
+    Background,
+    /// Filling in extra data (wish counts) for results already on screen
+    Enrich,
+    /// Searches and item pages the user is looking at right now
+    #[default]
+    User,
+}
+
+#[derive(Debug, Clone)]
+pub struct SchedulerConfig {
+    /// Minimum gap between the start of two requests
+    pub min_interval: Duration,
+    /// Larger gap used before background requests
+    pub background_interval: Duration,
+    /// First cooldown after a 429/5xx; doubles on each consecutive failure
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            min_interval: Duration::from_millis(1000),
+            background_interval: Duration::from_millis(1500),
+            base_backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(120),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    /// Waiting requests ordered by (priority desc, arrival asc)
+    queue: BTreeSet<(std::cmp::Reverse<Priority>, u64)>,
+    next_ticket: u64,
+    last_start: Option<Instant>,
+    /// Shared by every queue: nobody talks to Booth before this instant
+    cooldown_until: Option<Instant>,
+    consecutive_failures: u32,
+}
+
+/// Global gate in front of every request to Booth.pm.
+///
+/// Callers `acquire` a slot before sending and report the outcome afterwards
+/// so that a 429 from one queue slows down all of them.
+pub struct RequestScheduler {
+    config: SchedulerConfig,
+    state: Mutex<State>,
+    notify: Notify,
+}
+
+impl RequestScheduler {
+    pub fn new(config: SchedulerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State {
+                queue: BTreeSet::new(),
+                next_ticket: 0,
+                last_start: None,
+                cooldown_until: None,
+                consecutive_failures: 0,
+            }),
+            notify: Notify::new(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        // State stays consistent even if a holder panicked, so recover it.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn interval_for(&self, priority: Priority) -> Duration {
+        match priority {
+            Priority::Background => self.config.background_interval,
+            _ => self.config.min_interval,
+        }
+    }
+
+    /// Wait until this request may be sent.
+    pub async fn acquire(&self, priority: Priority) {
+        let key = {
+            let mut state = self.lock();
+            let key = (std::cmp::Reverse(priority), state.next_ticket);
+            state.next_ticket += 1;
+            state.queue.insert(key);
+            key
+        };
+        // Leaves the queue even if the caller's future is dropped mid-wait
+        let _guard = QueueGuard {
+            scheduler: self,
+            key,
+        };
+
+        loop {
+            let mut notified = std::pin::pin!(self.notify.notified());
+            notified.as_mut().enable();
+
+            let wait = {
+                let mut state = self.lock();
+                if state.queue.first() != Some(&key) {
+                    None
+                } else {
+                    let now = Instant::now();
+                    let mut ready_at = state
+                        .last_start
+                        .map(|t| t + self.interval_for(priority))
+                        .unwrap_or(now);
+                    if let Some(cooldown) = state.cooldown_until {
+                        ready_at = ready_at.max(cooldown);
+                    }
+                    if ready_at <= now {
+                        state.last_start = Some(now);
+                        return;
+                    }
+                    Some(ready_at - now)
+                }
+            };
+
+            match wait {
+                // Head of the queue: sleep until our slot, but wake early if a
+                // higher-priority request arrives or the cooldown changes.
+                Some(delay) => {
+                    let _ = tokio::time::timeout(delay, notified).await;
+                }
+                None => notified.await,
+            }
+        }
+    }
+
+    /// Reset the backoff after a successful response.
+    pub fn record_success(&self) {
+        let mut state = self.lock();
+        state.consecutive_failures = 0;
+    }
+
+    /// Start (or extend) the shared cooldown after a 429 or 5xx response.
+    /// Returns how long everyone will wait.
+    pub fn record_failure(&self, retry_after: Option<Duration>) -> Duration {
+        let delay = {
+            let mut state = self.lock();
+            let exp = state.consecutive_failures.min(16);
+            state.consecutive_failures += 1;
+            let backoff = self
+                .config
+                .base_backoff
+                .saturating_mul(1 << exp)
+                .min(self.config.max_backoff);
+            let delay = retry_after.map_or(backoff, |r| r.min(MAX_RETRY_AFTER).max(backoff));
+            let until = Instant::now() + delay;
+            state.cooldown_until = Some(state.cooldown_until.map_or(until, |c| c.max(until)));
+            delay
+        };
+        self.notify.notify_waiters();
+        delay
+    }
+}
+
+impl Default for RequestScheduler {
+    fn default() -> Self {
+        Self::new(SchedulerConfig::default())
+    }
+}
+
+struct QueueGuard<'a> {
+    scheduler: &'a RequestScheduler,
+    key: (std::cmp::Reverse<Priority>, u64),
+}
+
+impl Drop for QueueGuard<'_> {
+    fn drop(&mut self) {
+        self.scheduler.lock().queue.remove(&self.key);
+        self.scheduler.notify.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn fast_config() -> SchedulerConfig {
+        SchedulerConfig {
+            min_interval: Duration::from_millis(40),
+            background_interval: Duration::from_millis(60),
+            base_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(400),
+        }
+    }
+
+    #[test]
+    fn spaces_requests_by_min_interval() {
+        tauri::async_runtime::block_on(async {
+            let scheduler = RequestScheduler::new(fast_config());
+            let start = Instant::now();
+            for _ in 0..3 {
+                scheduler.acquire(Priority::User).await;
+            }
+            assert!(start.elapsed() >= Duration::from_millis(80));
+        });
+    }
+
+    #[test]
+    fn user_requests_jump_ahead_of_background() {
+        tauri::async_runtime::block_on(async {
+            let scheduler = Arc::new(RequestScheduler::new(fast_config()));
+            // Occupy the current slot so the next requests have to queue
+            scheduler.acquire(Priority::User).await;
+
+            let order = Arc::new(Mutex::new(Vec::new()));
+            let mut handles = Vec::new();
+            for (label, priority) in [
+                ("background", Priority::Background),
+                ("enrich", Priority::Enrich),
+                ("user", Priority::User),
+            ] {
+                let scheduler = scheduler.clone();
+                let order = order.clone();
+                handles.push(tauri::async_runtime::spawn(async move {
+                    scheduler.acquire(priority).await;
+                    order.lock().unwrap().push(label);
+                }));
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            for h in handles {
+                h.await.unwrap();
+            }
+            assert_eq!(*order.lock().unwrap(), vec!["user", "enrich", "background"]);
+        });
+    }
+
+    #[test]
+    fn failures_back_off_exponentially_and_honor_retry_after() {
+        let scheduler = RequestScheduler::new(fast_config());
+        assert_eq!(scheduler.record_failure(None), Duration::from_millis(100));
+        assert_eq!(scheduler.record_failure(None), Duration::from_millis(200));
+        assert_eq!(scheduler.record_failure(None), Duration::from_millis(400));
+        // Capped at max_backoff
+        assert_eq!(scheduler.record_failure(None), Duration::from_millis(400));
+        // Retry-After wins when it is longer than our own backoff
+        assert_eq!(
+            scheduler.record_failure(Some(Duration::from_secs(5))),
+            Duration::from_secs(5)
+        );
+        scheduler.record_success();
+        assert_eq!(scheduler.record_failure(None), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn retry_after_is_clamped() {
+        let scheduler = RequestScheduler::new(fast_config());
+        assert_eq!(
+            scheduler.record_failure(Some(Duration::from_secs(86400))),
+            MAX_RETRY_AFTER
+        );
+        let cooldown = scheduler.lock().cooldown_until.unwrap();
+        assert!(cooldown <= Instant::now() + MAX_RETRY_AFTER);
+    }
+
+    #[test]
+    fn cooldown_is_shared_across_priorities() {
+        tauri::async_runtime::block_on(async {
+            let scheduler = RequestScheduler::new(fast_config());
+            scheduler.record_failure(Some(Duration::from_millis(150)));
+            let start = Instant::now();
+            scheduler.acquire(Priority::User).await;
+            assert!(start.elapsed() >= Duration::from_millis(140));
+        });
+    }
+}
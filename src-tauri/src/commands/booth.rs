@@ -0,0 +1,479 @@
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::booth::client::{normalize_shop_subdomain, BoothClient};
+use crate::booth::models::{
+    BoothItem, Locale, SearchParams, SearchResult, ShopInfo, ShopItemsResult,
+};
+use crate::booth::scheduler::Priority;
+use crate::database::AppDatabase;
+use crate::error::AppResult;
+
+use super::db::{
+    query_cached_items, read_cached_item, read_cached_shop, record_availability,
+    write_cached_items, write_shop, Availability,
+};
+
+/// Items with a background refresh in flight, so reopening one doesn't queue another
+static REFRESHING: Mutex<BTreeSet<i64>> = Mutex::new(BTreeSet::new());
+
+// ── Types ──────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct CachedItem {
+    pub item: BoothItem,
+    /// `None` when the item was just fetched because nothing was cached
+    pub cached_at: Option<String>,
+    /// A refresh is running; the result arrives as an `item-updated` event
+    pub stale: bool,
+}
+
+// ── Commands ───────────────────────────────────────────
+
+/// Search Booth, falling back to `cached_items` (flagged `offline`) when Booth
+/// can't be reached. The original error is returned if the cache has nothing.
+/// Only `User` searches fall back: background callers such as the avatar
+/// updater store what they get, and must not take cache counts for Booth's.
+#[tauri::command]
+pub async fn search_booth(
+    client: State<'_, BoothClient>,
+    db: State<'_, AppDatabase>,
+    params: SearchParams,
+    priority: Option<Priority>,
+) -> AppResult<SearchResult> {
+    search_or_cached(&client, &db, &params, priority.unwrap_or_default()).await
+}
+
+async fn search_or_cached(
+    client: &BoothClient,
+    db: &AppDatabase,
+    params: &SearchParams,
+    priority: Priority,
+) -> AppResult<SearchResult> {
+    let err = match client.search(params, priority).await {
+        Ok(result) => return Ok(result),
+        Err(e) if e.retryable() && priority == Priority::User => e,
+        Err(e) => return Err(e),
+    };
+
+    let cached = query_cached_items(&*db.conn()?, params)?;
+    if cached.items.is_empty() {
+        return Err(err);
+    }
+    log::warn!("Booth unreachable ({}), serving cached results", err);
+    Ok(cached)
+}
+
+#[tauri::command]
+pub async fn get_booth_item(
+    client: State<'_, BoothClient>,
+    db: State<'_, AppDatabase>,
+    item_id: i64,
+    locale: Option<Locale>,
+    priority: Option<Priority>,
+) -> AppResult<BoothItem> {
+    fetch_and_cache_item(
+        &client,
+        &db,
+        item_id,
+        locale.unwrap_or_default(),
+        priority.unwrap_or_default(),
+    )
+    .await
+}
+
+/// Fetch an item from Booth and write it to `cached_items`, updating the
+/// favorite's availability from the outcome. A failed cache write is logged,
+/// not returned: the caller still gets the fresh item.
+pub(crate) async fn fetch_and_cache_item(
+    client: &BoothClient,
+    db: &AppDatabase,
+    item_id: i64,
+    locale: Locale,
+    priority: Priority,
+) -> AppResult<BoothItem> {
+    let result = client.get_item(item_id, locale, priority).await;
+    if let Some(availability) = Availability::from_fetch(&result) {
+        let recorded = db
+            .conn()
+            .and_then(|conn| record_availability(&conn, item_id, availability));
+        if let Err(e) = recorded {
+            log::warn!("Failed to record availability of item {}: {}", item_id, e);
+        }
+    }
+    // A failed fetch leaves the cached row alone, so a delisted item keeps
+    // its last known description and images.
+    let item = result?;
+    let saved = db
+        .conn_mut()
+        .and_then(|mut conn| write_cached_items(&mut conn, std::slice::from_ref(&item), true));
+    if let Err(e) = saved {
+        log::warn!("Failed to cache item {}: {}", item_id, e);
+    }
+    Ok(item)
+}
+
+/// Item detail with stale-while-revalidate: a fresh cached row is returned as is,
+/// a stale one is returned immediately and refreshed in the background, and a
+/// miss is fetched from Booth before returning.
+#[tauri::command]
+pub async fn get_cached_item(
+    app: AppHandle,
+    client: State<'_, BoothClient>,
+    db: State<'_, AppDatabase>,
+    item_id: i64,
+    locale: Option<Locale>,
+) -> AppResult<CachedItem> {
+    let cached = read_cached_item(&*db.conn()?, item_id)?;
+    let Some(row) = cached else {
+        let item = fetch_and_cache_item(
+            &client,
+            &db,
+            item_id,
+            locale.unwrap_or_default(),
+            Priority::User,
+        )
+        .await?;
+        return Ok(CachedItem {
+            item,
+            cached_at: None,
+            stale: false,
+        });
+    };
+
+    let stale = !row.fresh;
+    if stale && refreshing().insert(item_id) {
+        let locale = locale.unwrap_or(row.item.locale);
+        tauri::async_runtime::spawn(refresh_item(app, item_id, locale));
+    }
+    Ok(CachedItem {
+        item: row.item,
+        cached_at: Some(row.cached_at),
+        stale,
+    })
+}
+
+fn refreshing() -> std::sync::MutexGuard<'static, BTreeSet<i64>> {
+    REFRESHING.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+async fn refresh_item(app: AppHandle, item_id: i64, locale: Locale) {
+    let client = app.state::<BoothClient>();
+    let db = app.state::<AppDatabase>();
+
+    match fetch_and_cache_item(&client, &db, item_id, locale, Priority::Enrich).await {
+        Ok(item) => {
+            let _ = app.emit("item-updated", item);
+        }
+        Err(e) => log::warn!("Background refresh of item {} failed: {}", item_id, e),
+    }
+    refreshing().remove(&item_id);
+}
+
+/// List one page of a shop's items. The shop header is cached in `shops`;
+/// when a page comes back without one, the cached profile is used instead.
+#[tauri::command]
+pub async fn get_shop_items(
+    client: State<'_, BoothClient>,
+    db: State<'_, AppDatabase>,
+    subdomain: String,
+    page: Option<u32>,
+    locale: Option<Locale>,
+    priority: Option<Priority>,
+) -> AppResult<ShopItemsResult> {
+    fetch_shop_items(
+        &client,
+        &db,
+        &subdomain,
+        page.unwrap_or(1).max(1),
+        locale.unwrap_or_default(),
+        priority.unwrap_or_default(),
+    )
+    .await
+}
+
+async fn fetch_shop_items(
+    client: &BoothClient,
+    db: &AppDatabase,
+    subdomain: &str,
+    page: u32,
+    locale: Locale,
+    priority: Priority,
+) -> AppResult<ShopItemsResult> {
+    let subdomain = normalize_shop_subdomain(subdomain)?;
+    let parsed = client
+        .get_shop_page(&subdomain, page, locale, priority)
+        .await?;
+
+    let mut conn = db.conn_mut()?;
+    let shop = match parsed.shop {
+        Some(shop) => {
+            if let Err(e) = write_shop(&conn, &shop) {
+                log::warn!("Failed to cache shop {}: {}", subdomain, e);
+            }
+            shop
+        }
+        None => read_cached_shop(&conn, &subdomain)?.unwrap_or_else(|| {
+            log::warn!("No shop header found for {}", subdomain);
+            ShopInfo {
+                subdomain: subdomain.clone(),
+                name: subdomain.clone(),
+                icon_url: None,
+                description: None,
+                item_count: None,
+            }
+        }),
+    };
+    let items: Vec<BoothItem> = parsed
+        .items
+        .into_iter()
+        .map(|item| BoothItem {
+            shop_name: item.shop_name.or_else(|| Some(shop.name.clone())),
+            ..item
+        })
+        .collect();
+    if let Err(e) = write_cached_items(&mut conn, &items, false) {
+        log::warn!("Failed to cache items for shop {}: {}", subdomain, e);
+    }
+
+    Ok(ShopItemsResult {
+        shop,
+        items,
+        current_page: page,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booth::mock_server::{MockBooth, MockResponse, ITEM_JSON, SHOP_HTML};
+    use crate::commands::db::{read_favorites, FavoritesFilter};
+    use crate::error::AppError;
+
+    fn kipfel_search() -> SearchParams {
+        SearchParams {
+            keyword: "ワンピース".into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn search_serves_cache_when_booth_is_unavailable() {
+        let server = MockBooth::start();
+        server.on(
+            "booth.test/ja/items?q=%E3%83%AF%E3%83%B3%E3%83%94%E3%83%BC%E3%82%B9&page=1",
+            MockResponse::status(503),
+        );
+        let client = server.client(&[]);
+        let db = AppDatabase::open_in_memory().unwrap();
+
+        // Nothing cached yet: the Booth error comes through
+        let err = tauri::async_runtime::block_on(search_or_cached(
+            &client,
+            &db,
+            &kipfel_search(),
+            Priority::User,
+        ))
+        .unwrap_err();
+        assert!(matches!(err, AppError::HttpStatus { status: 503, .. }));
+
+        server
+            .on(
+                "booth.test/ja/items/5123456.json",
+                MockResponse::json(ITEM_JSON),
+            )
+            .on(
+                "booth.test/ja/items/5123456.json",
+                MockResponse::status(404),
+            );
+        tauri::async_runtime::block_on(fetch_and_cache_item(
+            &client,
+            &db,
+            5123456,
+            Locale::Ja,
+            Priority::User,
+        ))
+        .unwrap();
+
+        let result = tauri::async_runtime::block_on(search_or_cached(
+            &client,
+            &db,
+            &kipfel_search(),
+            Priority::User,
+        ))
+        .unwrap();
+        assert!(result.offline);
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].id, 5123456);
+
+        // Background searches get the error, not the cached rows
+        let err = tauri::async_runtime::block_on(search_or_cached(
+            &client,
+            &db,
+            &kipfel_search(),
+            Priority::Background,
+        ))
+        .unwrap_err();
+        assert!(matches!(err, AppError::HttpStatus { status: 503, .. }));
+    }
+
+    #[test]
+    fn search_does_not_hide_non_retryable_errors() {
+        let server = MockBooth::start();
+        let db = AppDatabase::open_in_memory().unwrap();
+        // Unregistered targets are 404s
+        let err = tauri::async_runtime::block_on(search_or_cached(
+            &server.client(&[]),
+            &db,
+            &kipfel_search(),
+            Priority::User,
+        ))
+        .unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[test]
+    fn fetched_items_are_cached_fresh() {
+        // A later edit removes the description and variations
+        let mut edited: serde_json::Value = serde_json::from_str(ITEM_JSON).unwrap();
+        edited["description"] = "".into();
+        edited["variations"] = serde_json::json!([]);
+        let server = MockBooth::start();
+        server
+            .on(
+                "booth.test/ja/items/5123456.json",
+                MockResponse::json(ITEM_JSON),
+            )
+            .on(
+                "booth.test/ja/items/5123456.json",
+                MockResponse::json(&edited.to_string()),
+            );
+        let db = AppDatabase::open_in_memory().unwrap();
+
+        tauri::async_runtime::block_on(fetch_and_cache_item(
+            &server.client(&[]),
+            &db,
+            5123456,
+            Locale::Ja,
+            Priority::User,
+        ))
+        .unwrap();
+
+        let row = read_cached_item(&db.conn().unwrap(), 5123456)
+            .unwrap()
+            .expect("item should be cached");
+        assert!(row.fresh);
+        assert_eq!(row.item.wish_lists_count, Some(842));
+
+        // Refetched after the edit: still a fresh detail, stale fields cleared
+        db.conn()
+            .unwrap()
+            .execute("UPDATE cached_items SET detail_cached_at = NULL", [])
+            .unwrap();
+        tauri::async_runtime::block_on(fetch_and_cache_item(
+            &server.client(&[]),
+            &db,
+            5123456,
+            Locale::Ja,
+            Priority::User,
+        ))
+        .unwrap();
+
+        let row = read_cached_item(&db.conn().unwrap(), 5123456)
+            .unwrap()
+            .unwrap();
+        assert!(row.fresh);
+        assert_eq!(row.item.description, None);
+        assert!(row.item.variations.is_empty());
+    }
+
+    #[test]
+    fn shop_items_cache_the_shop_and_its_items() {
+        let server = MockBooth::start();
+        server.on(
+            "kipfel-shop.booth.test/items?page=1",
+            MockResponse::html(SHOP_HTML),
+        );
+        let db = AppDatabase::open_in_memory().unwrap();
+
+        let result = tauri::async_runtime::block_on(fetch_shop_items(
+            &server.client(&["kipfel-shop"]),
+            &db,
+            "https://Kipfel-Shop.booth.pm/items",
+            1,
+            Locale::Ja,
+            Priority::User,
+        ))
+        .unwrap();
+        assert_eq!(result.shop.name, "Kipfel Shop");
+        assert_eq!(result.items.len(), 2);
+
+        let conn = db.conn().unwrap();
+        let shop = read_cached_shop(&conn, "kipfel-shop").unwrap().unwrap();
+        assert_eq!(shop.item_count, Some(42));
+        let free = read_cached_item(&conn, result.items[1].id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(free.item.shop_name.as_deref(), Some("Kipfel Shop"));
+    }
+
+    #[test]
+    fn favorite_availability_follows_fetch_outcome() {
+        let server = MockBooth::start();
+        let client = server.client(&[]);
+        let db = AppDatabase::open_in_memory().unwrap();
+        db.conn()
+            .unwrap()
+            .execute(
+                "INSERT INTO favorites (item_id, name, price) VALUES (5123456, 'fav', 1500)",
+                [],
+            )
+            .unwrap();
+        let availability = || {
+            let filter = FavoritesFilter::default();
+            read_favorites(&db.conn().unwrap(), &filter).unwrap()[0].availability
+        };
+        assert_eq!(availability(), Availability::Unknown);
+
+        server
+            .on(
+                "booth.test/ja/items/5123456.json",
+                MockResponse::json(ITEM_JSON),
+            )
+            .on(
+                "booth.test/ja/items/5123456.json",
+                MockResponse::status(404),
+            );
+        tauri::async_runtime::block_on(fetch_and_cache_item(
+            &client,
+            &db,
+            5123456,
+            Locale::Ja,
+            Priority::User,
+        ))
+        .unwrap();
+        assert_eq!(availability(), Availability::Available);
+
+        // Delisted: unknown targets, the HTML page included, 404 too
+        let err = tauri::async_runtime::block_on(fetch_and_cache_item(
+            &client,
+            &db,
+            5123456,
+            Locale::Ja,
+            Priority::User,
+        ))
+        .unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+        assert_eq!(availability(), Availability::NotFound);
+
+        // The last good copy stays in the cache
+        let row = read_cached_item(&db.conn().unwrap(), 5123456)
+            .unwrap()
+            .unwrap();
+        assert!(row.item.description.is_some());
+        assert!(!row.item.images.is_empty());
+    }
+}
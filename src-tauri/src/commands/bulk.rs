@@ -0,0 +1,350 @@
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use tauri::State;
+
+use crate::database::AppDatabase;
+use crate::error::{AppError, AppResult};
+
+use super::collections::{ensure_collection, normalize_tag};
+use super::db::{delete_favorite, insert_favorite, AddFavoriteParams};
+
+/// Upper bound for one bulk call, so a runaway selection can't hold the
+/// database lock for long
+const MAX_BULK_ITEMS: usize = 5000;
+
+// ── Types ──────────────────────────────────────────────
+
+/// Outcome for one item of a bulk command.
+#[derive(Debug, Serialize)]
+pub struct BulkItemResult {
+    pub item_id: i64,
+    /// Whether anything changed; `false` for no-ops like re-adding a favorite
+    pub changed: bool,
+    /// Set when this item failed; its changes were rolled back, the others kept
+    pub error: Option<AppError>,
+}
+
+// ── Commands ───────────────────────────────────────────
+
+#[tauri::command]
+pub fn add_favorites_bulk(
+    db: State<'_, AppDatabase>,
+    items: Vec<AddFavoriteParams>,
+) -> AppResult<Vec<BulkItemResult>> {
+    run_bulk(&mut *db.conn_mut()?, &items, |p| p.item_id, insert_favorite)
+}
+
+#[tauri::command]
+pub fn remove_favorites_bulk(
+    db: State<'_, AppDatabase>,
+    item_ids: Vec<i64>,
+) -> AppResult<Vec<BulkItemResult>> {
+    run_bulk(
+        &mut *db.conn_mut()?,
+        &item_ids,
+        |id| *id,
+        |conn, id| delete_favorite(conn, *id),
+    )
+}
+
+/// Add `tags` to every item, keeping the tags each item already has.
+#[tauri::command]
+pub fn add_tags_bulk(
+    db: State<'_, AppDatabase>,
+    item_ids: Vec<i64>,
+    tags: Vec<String>,
+) -> AppResult<Vec<BulkItemResult>> {
+    add_tags(&mut *db.conn_mut()?, &item_ids, &tags)
+}
+
+/// Remove `tags` from every item, keeping their other tags.
+#[tauri::command]
+pub fn remove_tags_bulk(
+    db: State<'_, AppDatabase>,
+    item_ids: Vec<i64>,
+    tags: Vec<String>,
+) -> AppResult<Vec<BulkItemResult>> {
+    remove_tags(&mut *db.conn_mut()?, &item_ids, &tags)
+}
+
+/// Put items into `collection_id`, taking them out of `from_collection_id`
+/// when given. Without it the items stay in their other collections.
+#[tauri::command]
+pub fn move_items_to_collection(
+    db: State<'_, AppDatabase>,
+    item_ids: Vec<i64>,
+    collection_id: i64,
+    from_collection_id: Option<i64>,
+) -> AppResult<Vec<BulkItemResult>> {
+    move_items(
+        &mut *db.conn_mut()?,
+        &item_ids,
+        collection_id,
+        from_collection_id,
+    )
+}
+
+fn add_tags(
+    conn: &mut Connection,
+    item_ids: &[i64],
+    tags: &[String],
+) -> AppResult<Vec<BulkItemResult>> {
+    let tags = valid_tags(tags)?;
+    run_bulk(
+        conn,
+        item_ids,
+        |id| *id,
+        |conn, id| {
+            let mut added = 0;
+            for tag in &tags {
+                added += conn.execute(
+                    "INSERT OR IGNORE INTO item_tags (item_id, tag) VALUES (?1, ?2)",
+                    params![id, tag],
+                )?;
+            }
+            Ok(added > 0)
+        },
+    )
+}
+
+fn remove_tags(
+    conn: &mut Connection,
+    item_ids: &[i64],
+    tags: &[String],
+) -> AppResult<Vec<BulkItemResult>> {
+    let tags = valid_tags(tags)?;
+    run_bulk(
+        conn,
+        item_ids,
+        |id| *id,
+        |conn, id| {
+            let mut removed = 0;
+            for tag in &tags {
+                removed += conn.execute(
+                    "DELETE FROM item_tags WHERE item_id = ?1 AND tag = ?2",
+                    params![id, tag],
+                )?;
+            }
+            Ok(removed > 0)
+        },
+    )
+}
+
+fn move_items(
+    conn: &mut Connection,
+    item_ids: &[i64],
+    collection_id: i64,
+    from_collection_id: Option<i64>,
+) -> AppResult<Vec<BulkItemResult>> {
+    ensure_collection(conn, collection_id)?;
+    // Moving within the same collection is only an add
+    let from = from_collection_id.filter(|from| *from != collection_id);
+    run_bulk(
+        conn,
+        item_ids,
+        |id| *id,
+        |conn, id| {
+            let mut changed = conn.execute(
+                "INSERT OR IGNORE INTO collection_items (collection_id, item_id) VALUES (?1, ?2)",
+                params![collection_id, id],
+            )?;
+            if let Some(from) = from {
+                changed += conn.execute(
+                    "DELETE FROM collection_items WHERE collection_id = ?1 AND item_id = ?2",
+                    params![from, id],
+                )?;
+            }
+            Ok(changed > 0)
+        },
+    )
+}
+
+/// Normalized, deduplicated tags; an error when none are usable.
+fn valid_tags(tags: &[String]) -> AppResult<Vec<&str>> {
+    let mut valid: Vec<&str> = tags.iter().filter_map(|t| normalize_tag(t)).collect();
+    valid.sort_unstable();
+    valid.dedup();
+    if valid.is_empty() {
+        return Err(AppError::Validation("No valid tags given".to_string()));
+    }
+    Ok(valid)
+}
+
+/// Apply `op` to each item in one transaction. Each item runs in its own
+/// savepoint: a failing item is rolled back and reported, the rest commit.
+/// Database failures outside the items (begin, commit) fail the whole call.
+fn run_bulk<T>(
+    conn: &mut Connection,
+    items: &[T],
+    item_id: impl Fn(&T) -> i64,
+    mut op: impl FnMut(&Connection, &T) -> AppResult<bool>,
+) -> AppResult<Vec<BulkItemResult>> {
+    if items.len() > MAX_BULK_ITEMS {
+        return Err(AppError::Validation(format!(
+            "Too many items (max {})",
+            MAX_BULK_ITEMS
+        )));
+    }
+    let mut tx = conn.transaction()?;
+    let mut results = Vec::with_capacity(items.len());
+    for item in items {
+        let savepoint = tx.savepoint()?;
+        let (changed, error) = match op(&savepoint, item) {
+            Ok(changed) => {
+                savepoint.commit()?;
+                (changed, None)
+            }
+            // Dropping the savepoint rolls it back
+            Err(e) => (false, Some(e)),
+        };
+        results.push(BulkItemResult {
+            item_id: item_id(item),
+            changed,
+            error,
+        });
+    }
+    tx.commit()?;
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags_of(conn: &Connection, item_id: i64) -> Vec<String> {
+        let mut stmt = conn
+            .prepare("SELECT tag FROM item_tags WHERE item_id = ?1 ORDER BY tag")
+            .unwrap();
+        stmt.query_map(params![item_id], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    fn changed(results: &[BulkItemResult]) -> Vec<bool> {
+        results.iter().map(|r| r.changed).collect()
+    }
+
+    fn favorite(item_id: i64) -> AddFavoriteParams {
+        AddFavoriteParams {
+            item_id,
+            name: format!("item {}", item_id),
+            price: 500,
+            thumbnail_url: None,
+            category_name: None,
+            shop_name: None,
+        }
+    }
+
+    #[test]
+    fn tags_are_added_and_removed_without_replacing_others() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        let mut conn = db.conn_mut().unwrap();
+        conn.execute(
+            "INSERT INTO item_tags (item_id, tag) VALUES (1, 'kept')",
+            [],
+        )
+        .unwrap();
+
+        let tags = vec![" new ".to_string(), "new".to_string(), "".to_string()];
+        let results = add_tags(&mut conn, &[1, 2], &tags).unwrap();
+        assert_eq!(changed(&results), vec![true, true]);
+        assert_eq!(tags_of(&conn, 1), vec!["kept", "new"]);
+        assert_eq!(tags_of(&conn, 2), vec!["new"]);
+        // Already tagged
+        assert_eq!(
+            changed(&add_tags(&mut conn, &[1], &tags).unwrap()),
+            vec![false]
+        );
+
+        let results = remove_tags(&mut conn, &[1, 3], &["new".to_string()]).unwrap();
+        assert_eq!(changed(&results), vec![true, false]);
+        assert_eq!(tags_of(&conn, 1), vec!["kept"]);
+
+        assert!(matches!(
+            add_tags(&mut conn, &[1], &["  ".to_string()]),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn items_move_between_collections() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        let mut conn = db.conn_mut().unwrap();
+        conn.execute_batch(
+            "INSERT INTO collections (id, name) VALUES (1, 'inbox'), (2, 'done');
+             INSERT INTO collection_items (collection_id, item_id) VALUES (1, 10), (1, 11), (2, 11);",
+        )
+        .unwrap();
+
+        let results = move_items(&mut conn, &[10, 11], 2, Some(1)).unwrap();
+        assert_eq!(changed(&results), vec![true, true]);
+        let members: Vec<(i64, i64)> = conn
+            .prepare("SELECT collection_id, item_id FROM collection_items ORDER BY 1, 2")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(members, vec![(2, 10), (2, 11)]);
+
+        assert!(matches!(
+            move_items(&mut conn, &[10], 99, None),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn favorites_bulk_reports_each_item() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        let mut conn = db.conn_mut().unwrap();
+
+        let items = vec![favorite(1), favorite(2), favorite(1)];
+        let results = run_bulk(&mut conn, &items, |p| p.item_id, insert_favorite).unwrap();
+        assert_eq!(changed(&results), vec![true, true, false]);
+
+        conn.execute("INSERT INTO item_tags (item_id, tag) VALUES (1, 'x')", [])
+            .unwrap();
+        let results = run_bulk(
+            &mut conn,
+            &[1, 3],
+            |id| *id,
+            |conn, id| delete_favorite(conn, *id),
+        )
+        .unwrap();
+        assert_eq!(changed(&results), vec![true, false]);
+        assert!(tags_of(&conn, 1).is_empty());
+    }
+
+    #[test]
+    fn failed_items_roll_back_alone() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        let mut conn = db.conn_mut().unwrap();
+
+        let items = vec![favorite(1), favorite(2), favorite(3)];
+        let results = run_bulk(
+            &mut conn,
+            &items,
+            |p| p.item_id,
+            |conn, p| {
+                insert_favorite(conn, p)?;
+                if p.item_id == 2 {
+                    return Err(AppError::Validation("rejected".to_string()));
+                }
+                Ok(true)
+            },
+        )
+        .unwrap();
+        assert!(results[1].error.is_some());
+        assert!(results[0].error.is_none() && results[2].error.is_none());
+
+        let ids: Vec<i64> = conn
+            .prepare("SELECT item_id FROM favorites ORDER BY item_id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(ids, vec![1, 3]);
+    }
+}
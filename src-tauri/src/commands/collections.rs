@@ -1,21 +1,24 @@
-use rusqlite::params;
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection};
 use serde::{Deserialize, Serialize};
 use tauri::State;
 
 use crate::database::AppDatabase;
 use crate::error::{AppError, AppResult};
 
-use super::db::FavoriteItem;
+use super::db::{
+    favorite_from_row, favorites_filter_sql, FavoriteItem, FavoritesFilter, FAVORITE_COLUMNS,
+};
 
 // ── Validation ────────────────────────────────────────
 
 fn validate_name(name: &str) -> AppResult<String> {
     let trimmed = name.trim().to_string();
     if trimmed.is_empty() {
-        return Err(AppError::ParseError("Name cannot be empty".to_string()));
+        return Err(AppError::Validation("Name cannot be empty".to_string()));
     }
     if trimmed.len() > 200 {
-        return Err(AppError::ParseError("Name too long (max 200 chars)".to_string()));
+        return Err(AppError::Validation("Name too long (max 200 chars)".to_string()));
     }
     Ok(trimmed)
 }
@@ -25,7 +28,26 @@ fn validate_color(color: &str) -> AppResult<()> {
         && color.starts_with('#')
         && color[1..].chars().all(|c| c.is_ascii_hexdigit());
     if !valid {
-        return Err(AppError::ParseError("Invalid color format (expected #RRGGBB)".to_string()));
+        return Err(AppError::Validation("Invalid color format (expected #RRGGBB)".to_string()));
+    }
+    Ok(())
+}
+
+/// Trimmed tag, or `None` for blank and overlong (100+ bytes) tags, which are skipped.
+pub(crate) fn normalize_tag(tag: &str) -> Option<&str> {
+    let trimmed = tag.trim();
+    (!trimmed.is_empty() && trimmed.len() <= 100).then_some(trimmed)
+}
+
+/// Error unless collection `id` exists.
+pub(crate) fn ensure_collection(conn: &Connection, id: i64) -> AppResult<()> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM collections WHERE id = ?1)",
+        params![id],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        return Err(AppError::NotFound(format!("Collection {}", id)));
     }
     Ok(())
 }
@@ -169,30 +191,26 @@ pub fn remove_from_collection(
 pub fn get_collection_items(
     db: State<'_, AppDatabase>,
     collection_id: i64,
+    filter: Option<FavoritesFilter>,
 ) -> AppResult<Vec<FavoriteItem>> {
+    let (filter_conditions, filter_values) = favorites_filter_sql(&filter.unwrap_or_default())?;
+    let mut conditions = vec!["ci.collection_id = ?".to_string()];
+    conditions.extend(filter_conditions);
+    let mut values = vec![Value::Integer(collection_id)];
+    values.extend(filter_values);
+
     let conn = db.conn()?;
-    let mut stmt = conn.prepare(
-        "SELECT f.id, f.item_id, f.name, f.price, f.thumbnail_url,
-                f.category_name, f.shop_name, f.added_at, f.note
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}
          FROM favorites f
          INNER JOIN collection_items ci ON ci.item_id = f.item_id
-         WHERE ci.collection_id = ?1
+         WHERE {}
          ORDER BY ci.added_at DESC",
-    )?;
+        FAVORITE_COLUMNS,
+        conditions.join(" AND ")
+    ))?;
     let rows = stmt
-        .query_map(params![collection_id], |row| {
-            Ok(FavoriteItem {
-                id: row.get(0)?,
-                item_id: row.get(1)?,
-                name: row.get(2)?,
-                price: row.get(3)?,
-                thumbnail_url: row.get(4)?,
-                category_name: row.get(5)?,
-                shop_name: row.get(6)?,
-                added_at: row.get(7)?,
-                note: row.get(8)?,
-            })
-        })?
+        .query_map(params_from_iter(values.iter()), favorite_from_row)?
         .collect::<Result<Vec<_>, _>>()?;
     Ok(rows)
 }
@@ -224,14 +242,10 @@ pub fn set_item_tags(
     let mut conn = db.conn_mut()?;
     let tx = conn.transaction()?;
     tx.execute("DELETE FROM item_tags WHERE item_id = ?1", params![item_id])?;
-    for tag in &tags {
-        let trimmed = tag.trim();
-        if trimmed.is_empty() || trimmed.len() > 100 {
-            continue;
-        }
+    for tag in tags.iter().filter_map(|t| normalize_tag(t)) {
         tx.execute(
             "INSERT OR IGNORE INTO item_tags (item_id, tag) VALUES (?1, ?2)",
-            params![item_id, trimmed],
+            params![item_id, tag],
         )?;
     }
     tx.commit()?;
@@ -0,0 +1,277 @@
+use std::collections::{BTreeSet, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::booth::client::{BoothClient, MAX_PAGE};
+use crate::booth::models::{BoothItem, SearchParams};
+use crate::booth::scheduler::Priority;
+use crate::database::AppDatabase;
+use crate::error::{AppError, AppResult};
+
+use super::db::write_cached_items;
+
+/// Upper bound for a single crawl, so a typo can't queue hours of requests.
+const MAX_CRAWL_PAGES: u32 = 100;
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+/// Crawls that are still running; cancelling removes the id.
+static ACTIVE_CRAWLS: Mutex<BTreeSet<u64>> = Mutex::new(BTreeSet::new());
+
+// ── Types ──────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct CrawlJob {
+    pub job_id: u64,
+    pub max_pages: u32,
+}
+
+/// Emitted as `search-crawl-page` after each page is fetched.
+#[derive(Debug, Clone, Serialize)]
+pub struct CrawlPage {
+    pub job_id: u64,
+    pub page: u32,
+    /// Only items not seen on an earlier page of this crawl
+    pub items: Vec<BoothItem>,
+    pub pages_fetched: u32,
+    pub total_items: usize,
+    pub total_count: Option<i64>,
+}
+
+/// Emitted as `search-crawl-done` once, however the crawl ends.
+#[derive(Debug, Clone, Serialize)]
+pub struct CrawlDone {
+    pub job_id: u64,
+    pub pages_fetched: u32,
+    pub total_items: usize,
+    /// Set when the crawl stopped early; cancellation reports `Cancelled`
+    pub error: Option<AppError>,
+}
+
+// ── Commands ───────────────────────────────────────────
+
+/// Walk consecutive search result pages in the background, starting at
+/// `params.page`.
+///
+/// Pages are fetched at `Background` priority so the user's own searches keep
+/// going first. Each page is written to `cached_items` and streamed through
+/// `search-crawl-page`; the crawl stops early when a page has no new items.
+#[tauri::command]
+pub fn crawl_search(app: AppHandle, params: SearchParams, max_pages: u32) -> AppResult<CrawlJob> {
+    if max_pages == 0 {
+        return Err(AppError::Validation(
+            "max_pages must be at least 1".to_string(),
+        ));
+    }
+    let max_pages = max_pages.min(MAX_CRAWL_PAGES);
+    let job_id = NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed);
+    active_crawls().insert(job_id);
+
+    tauri::async_runtime::spawn(run_crawl(app, job_id, params, max_pages));
+    Ok(CrawlJob { job_id, max_pages })
+}
+
+/// Stop a running crawl. A page that is already in flight is discarded.
+/// Returns `false` if the job had already finished.
+#[tauri::command]
+pub fn cancel_search_crawl(job_id: u64) -> bool {
+    active_crawls().remove(&job_id)
+}
+
+fn active_crawls() -> std::sync::MutexGuard<'static, BTreeSet<u64>> {
+    // A set of ids can't be left half-updated, so recover from poisoning.
+    ACTIVE_CRAWLS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn is_active(job_id: u64) -> bool {
+    active_crawls().contains(&job_id)
+}
+
+async fn run_crawl(app: AppHandle, job_id: u64, params: SearchParams, max_pages: u32) {
+    let client = app.state::<BoothClient>();
+    let db = app.state::<AppDatabase>();
+    let done = crawl_pages(&client, &db, job_id, &params, max_pages, |page| {
+        let _ = app.emit("search-crawl-page", page);
+    })
+    .await;
+    let _ = app.emit("search-crawl-done", done);
+}
+
+/// The crawl loop behind `run_crawl`; `on_page` receives each page with new items.
+async fn crawl_pages(
+    client: &BoothClient,
+    db: &AppDatabase,
+    job_id: u64,
+    params: &SearchParams,
+    max_pages: u32,
+    mut on_page: impl FnMut(CrawlPage),
+) -> CrawlDone {
+    let start = params.page.unwrap_or(1).max(1);
+    let mut seen: HashSet<i64> = HashSet::new();
+    let mut pages_fetched = 0;
+    let mut error = None;
+
+    for page in (start..start.saturating_add(max_pages)).take_while(|p| *p <= MAX_PAGE) {
+        if !is_active(job_id) {
+            error = Some(AppError::Cancelled);
+            break;
+        }
+
+        let page_params = SearchParams {
+            page: Some(page),
+            ..params.clone()
+        };
+        let result = match client.search(&page_params, Priority::Background).await {
+            Ok(result) => result,
+            Err(e) => {
+                log::warn!("Crawl {} stopped at page {}: {}", job_id, page, e);
+                error = Some(e);
+                break;
+            }
+        };
+        pages_fetched += 1;
+        // Cancelled while the request was in flight: drop the page
+        if !is_active(job_id) {
+            error = Some(AppError::Cancelled);
+            break;
+        }
+
+        let items: Vec<BoothItem> = result
+            .items
+            .into_iter()
+            .filter(|item| seen.insert(item.id))
+            .collect();
+        if items.is_empty() {
+            // Past the last page Booth repeats the final results or returns nothing
+            break;
+        }
+
+        let saved = db
+            .conn_mut()
+            .and_then(|mut conn| write_cached_items(&mut conn, &items, false));
+        if let Err(e) = saved {
+            log::warn!(
+                "Failed to cache crawl page {} of job {}: {}",
+                page,
+                job_id,
+                e
+            );
+        }
+
+        on_page(CrawlPage {
+            job_id,
+            page,
+            items,
+            pages_fetched,
+            total_items: seen.len(),
+            total_count: result.total_count,
+        });
+    }
+
+    // A cancel that raced with the last page still counts as a cancel
+    if !active_crawls().remove(&job_id) && error.is_none() {
+        error = Some(AppError::Cancelled);
+    }
+    CrawlDone {
+        job_id,
+        pages_fetched,
+        total_items: seen.len(),
+        error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booth::client::build_search_url;
+    use crate::booth::mock_server::{MockBooth, MockResponse, HOST, SEARCH_HTML};
+
+    /// Serve `html` as page `page` of the `衣装` search.
+    fn serve_page(server: &MockBooth, page: u32, html: &str) {
+        let url = build_search_url(&server.origin(), &search(Some(page)));
+        let path = url.strip_prefix(&server.origin()).unwrap();
+        server.on(&format!("{}{}", HOST, path), MockResponse::html(html));
+    }
+
+    fn search(page: Option<u32>) -> SearchParams {
+        SearchParams {
+            keyword: "衣装".into(),
+            page,
+            ..Default::default()
+        }
+    }
+
+    /// Run a crawl to completion, collecting each page's item ids.
+    fn crawl(
+        server: &MockBooth,
+        job_id: u64,
+        max_pages: u32,
+        mut on_page: impl FnMut(&CrawlPage),
+    ) -> (Vec<(u32, Vec<i64>)>, CrawlDone) {
+        let db = AppDatabase::open_in_memory().unwrap();
+        active_crawls().insert(job_id);
+        let mut pages = Vec::new();
+        let done = tauri::async_runtime::block_on(crawl_pages(
+            &server.client(&[]),
+            &db,
+            job_id,
+            &search(None),
+            max_pages,
+            |page| {
+                on_page(&page);
+                pages.push((page.page, page.items.iter().map(|i| i.id).collect()));
+            },
+        ));
+        (pages, done)
+    }
+
+    #[test]
+    fn pages_are_deduplicated_and_the_crawl_ends_when_nothing_is_new() {
+        let server = MockBooth::start();
+        let page2 = SEARCH_HTML.replace("4000001", "4000002");
+        serve_page(&server, 1, SEARCH_HTML);
+        serve_page(&server, 2, &page2);
+        serve_page(&server, 3, &page2);
+
+        let (pages, done) = crawl(&server, 1001, 10, |_| {});
+        assert_eq!(pages, vec![(1, vec![5123456, 4000001]), (2, vec![4000002])]);
+        assert_eq!(done.pages_fetched, 3);
+        assert_eq!(done.total_items, 3);
+        assert!(done.error.is_none());
+        assert!(!is_active(1001));
+    }
+
+    #[test]
+    fn crawl_stops_at_max_pages() {
+        let server = MockBooth::start();
+        for page in 1..=3 {
+            let html = SEARCH_HTML.replace("4000001", &format!("400000{}", page));
+            serve_page(&server, page, &html);
+        }
+
+        let (pages, done) = crawl(&server, 1002, 2, |_| {});
+        assert_eq!(pages.len(), 2);
+        assert_eq!(done.pages_fetched, 2);
+        assert!(done.error.is_none());
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn cancelled_crawl_stops_and_reports_it() {
+        let server = MockBooth::start();
+        for page in 1..=3 {
+            let html = SEARCH_HTML.replace("4000001", &format!("400000{}", page));
+            serve_page(&server, page, &html);
+        }
+
+        let (pages, done) = crawl(&server, 1003, 10, |_| {
+            cancel_search_crawl(1003);
+        });
+        assert_eq!(pages.len(), 1);
+        assert_eq!(done.pages_fetched, 1);
+        assert!(matches!(done.error, Some(AppError::Cancelled)));
+        assert_eq!(server.requests().len(), 1);
+    }
+}
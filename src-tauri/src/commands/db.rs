@@ -1,10 +1,16 @@
-use rusqlite::params;
+use std::collections::HashMap;
+
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Row};
 use serde::{Deserialize, Serialize};
 use tauri::State;
 
-use crate::booth::models::BoothItem;
+use crate::booth::models::{
+    category_key, BoothItem, Locale, SearchParams, SearchResult, ShopInfo, StockStatus, Variation,
+    VariationKind,
+};
 use crate::database::AppDatabase;
-use crate::error::AppResult;
+use crate::error::{AppError, AppResult};
 
 // ── Types ──────────────────────────────────────────────
 
@@ -19,6 +25,150 @@ pub struct FavoriteItem {
     pub shop_name: Option<String>,
     pub added_at: String,
     pub note: Option<String>,
+    pub note_updated_at: Option<String>,
+    /// Last time `refresh_favorites` re-fetched this item
+    pub refreshed_at: Option<String>,
+    pub availability: Availability,
+    pub last_checked_at: Option<String>,
+    /// Bought, as opposed to only wishlisted; the purchase fields are unset otherwise
+    pub owned: bool,
+    /// `YYYY-MM-DD`
+    pub purchased_at: Option<String>,
+    pub price_paid: Option<i64>,
+    pub purchased_variation: Option<String>,
+    /// Booth order number or any other reference to the purchase
+    pub order_ref: Option<String>,
+    /// Latest price seen in `price_history`, if any
+    pub current_price: Option<i64>,
+    /// Price when the item was favorited
+    pub added_price: i64,
+    /// The item is cheaper now than when it was favorited
+    pub price_dropped: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PricePoint {
+    pub price: i64,
+    pub observed_at: String,
+}
+
+/// Whether a favorite's Booth page still works, as of `last_checked_at`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Availability {
+    Available,
+    /// Booth answered 404: deleted or never existed
+    NotFound,
+    /// Booth redirected away from the item page
+    Private,
+    /// Not checked yet, or the last check failed for another reason
+    #[default]
+    Unknown,
+}
+
+impl Availability {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Availability::Available => "available",
+            Availability::NotFound => "not_found",
+            Availability::Private => "private",
+            Availability::Unknown => "unknown",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Availability {
+        match code {
+            "available" => Availability::Available,
+            "not_found" => Availability::NotFound,
+            "private" => Availability::Private,
+            _ => Availability::Unknown,
+        }
+    }
+
+    /// What an item fetch says about availability; `None` when the failure
+    /// (network, rate limit...) says nothing about the item itself.
+    pub fn from_fetch<T>(result: &AppResult<T>) -> Option<Availability> {
+        match result {
+            Ok(_) => Some(Availability::Available),
+            Err(AppError::NotFound(_)) => Some(Availability::NotFound),
+            Err(AppError::Private(_)) => Some(Availability::Private),
+            Err(_) => None,
+        }
+    }
+}
+
+/// Optional narrowing for the favorites queries; unset fields match everything.
+#[derive(Debug, Default, Deserialize)]
+pub struct FavoritesFilter {
+    /// Whitespace-separated terms that must each appear in the name, shop,
+    /// category, note or one of the user's tags
+    pub text: Option<String>,
+    /// Whitespace-separated terms that must all appear in the note
+    pub note: Option<String>,
+    /// User tags, matched exactly
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub tag_match: TagMatch,
+    pub collection_id: Option<i64>,
+    pub price_min: Option<i64>,
+    pub price_max: Option<i64>,
+    pub shop: Option<String>,
+    pub category: Option<String>,
+    /// `YYYY-MM-DD`, inclusive, compared with `added_at`
+    pub added_from: Option<String>,
+    /// `YYYY-MM-DD`, inclusive, compared with `added_at`
+    pub added_to: Option<String>,
+    pub availability: Option<Availability>,
+    /// `true` for owned items only, `false` for the wishlist only
+    pub owned: Option<bool>,
+}
+
+/// How `FavoritesFilter::tags` combine.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagMatch {
+    #[default]
+    Any,
+    All,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FavoritesSort {
+    #[default]
+    AddedDesc,
+    AddedAsc,
+    PriceAsc,
+    PriceDesc,
+    Name,
+}
+
+/// A favorite with its tags and collections, as returned by `query_favorites`.
+#[derive(Debug, Serialize)]
+pub struct FavoriteEntry {
+    #[serde(flatten)]
+    pub item: FavoriteItem,
+    pub tags: Vec<String>,
+    pub collection_ids: Vec<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FavoritesPage {
+    pub items: Vec<FavoriteEntry>,
+    /// Matches across all pages
+    pub total_count: i64,
+    pub offset: u32,
+    pub limit: u32,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct OwnershipParams {
+    /// `YYYY-MM-DD`; defaults to today
+    pub purchased_at: Option<String>,
+    pub price_paid: Option<i64>,
+    pub variation: Option<String>,
+    pub order_ref: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -47,8 +197,18 @@ pub struct PopularAvatar {
 #[tauri::command]
 pub fn cache_items(db: State<'_, AppDatabase>, items: Vec<BoothItem>) -> AppResult<()> {
     let mut conn = db.conn_mut()?;
+    write_cached_items(&mut conn, &items, false)
+}
+
+/// Upsert items into `cached_items` in a single transaction. `detail` marks
+/// item page fetches; search cards and shop listings pass `false`.
+pub(crate) fn write_cached_items(
+    conn: &mut Connection,
+    items: &[BoothItem],
+    detail: bool,
+) -> AppResult<()> {
     let tx = conn.transaction()?;
-    for item in &items {
+    for item in items {
         let images_json = serde_json::to_string(&item.images).unwrap_or_else(|e| {
             log::warn!("Failed to serialize images for item {}: {}", item.id, e);
             "[]".to_string()
@@ -57,28 +217,343 @@ pub fn cache_items(db: State<'_, AppDatabase>, items: Vec<BoothItem>) -> AppResu
             log::warn!("Failed to serialize tags for item {}: {}", item.id, e);
             "[]".to_string()
         });
+        // Search results carry no wish count and shop listings no category;
+        // keep the last known values instead of wiping them. Likewise a card
+        // must not clobber a detail fetch's description, full tag list and
+        // full-size images.
         tx.execute(
-            "INSERT OR REPLACE INTO cached_items
-             (id, name, description, price, category_name, shop_name, url, images_json, tags_json, wish_count, cached_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, datetime('now'))",
+            "INSERT INTO cached_items
+             (id, name, description, price, category_name, shop_name, url, images_json, tags_json,
+              wish_count, wish_count_at, locale, detail_cached_at, cached_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10,
+                     CASE WHEN ?10 IS NULL THEN NULL ELSE datetime('now') END, ?11,
+                     CASE WHEN ?12 THEN datetime('now') ELSE NULL END, datetime('now'))
+             ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                description = CASE WHEN ?12 OR cached_items.detail_cached_at IS NULL
+                    THEN excluded.description ELSE cached_items.description END,
+                price = excluded.price,
+                category_name = COALESCE(excluded.category_name, cached_items.category_name),
+                shop_name = excluded.shop_name,
+                url = excluded.url,
+                images_json = CASE WHEN ?12 OR cached_items.detail_cached_at IS NULL
+                    THEN excluded.images_json ELSE cached_items.images_json END,
+                tags_json = CASE WHEN ?12 OR cached_items.detail_cached_at IS NULL
+                    THEN excluded.tags_json ELSE cached_items.tags_json END,
+                wish_count = COALESCE(excluded.wish_count, cached_items.wish_count),
+                wish_count_at = COALESCE(excluded.wish_count_at, cached_items.wish_count_at),
+                locale = excluded.locale,
+                detail_cached_at = COALESCE(excluded.detail_cached_at, cached_items.detail_cached_at),
+                cached_at = excluded.cached_at",
             params![
                 item.id,
                 item.name,
                 item.description,
                 item.price,
-                item.category_name,
+                category_key(item.category_name.clone()),
                 item.shop_name,
                 item.url,
                 images_json,
                 tags_json,
                 item.wish_lists_count,
+                item.locale.as_str(),
+                detail,
             ],
         )?;
+
+        // Only detail fetches know the variations; search results keep the stored ones.
+        if detail {
+            tx.execute(
+                "DELETE FROM cached_item_variations WHERE item_id = ?1",
+                params![item.id],
+            )?;
+            for (position, v) in item.variations.iter().enumerate() {
+                tx.execute(
+                    "INSERT INTO cached_item_variations (item_id, position, name, price, kind, stock)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![
+                        item.id,
+                        position as i64,
+                        v.name,
+                        v.price,
+                        v.kind.as_str(),
+                        v.stock.as_str(),
+                    ],
+                )?;
+            }
+        }
+
+        record_price(&tx, item.id, item.price)?;
     }
     tx.commit()?;
     Ok(())
 }
 
+/// Store what a fetch of `item_id` revealed about its availability. Only
+/// favorites track it; other items are ignored.
+pub(crate) fn record_availability(
+    conn: &Connection,
+    item_id: i64,
+    availability: Availability,
+) -> AppResult<()> {
+    conn.execute(
+        "UPDATE favorites SET availability = ?2, last_checked_at = datetime('now')
+         WHERE item_id = ?1",
+        params![item_id, availability.as_str()],
+    )?;
+    Ok(())
+}
+
+/// Append `price` to a favorite's `price_history` if it differs from the last
+/// observation. Non-favorites are ignored.
+pub(crate) fn record_price(conn: &Connection, item_id: i64, price: i64) -> AppResult<()> {
+    conn.execute(
+        "INSERT INTO price_history (item_id, price)
+         SELECT ?1, ?2
+         WHERE EXISTS (SELECT 1 FROM favorites WHERE item_id = ?1)
+           AND ?2 IS NOT (SELECT price FROM price_history WHERE item_id = ?1
+                          ORDER BY observed_at DESC, id DESC LIMIT 1)",
+        params![item_id, price],
+    )?;
+    Ok(())
+}
+
+/// Matches Booth's own page size so offline paging lines up with online paging
+const OFFLINE_PAGE_SIZE: u32 = 60;
+
+/// Search `cached_items` instead of Booth. Results are flagged `offline`.
+#[tauri::command]
+pub fn search_cached_items(
+    db: State<'_, AppDatabase>,
+    params: SearchParams,
+) -> AppResult<SearchResult> {
+    let conn = db.conn()?;
+    query_cached_items(&conn, &params)
+}
+
+/// Serve keyword, category, tag, price and sort filters from the cache.
+/// Filters the cache can't answer (stock, adult, new arrivals) are ignored.
+pub(crate) fn query_cached_items(
+    conn: &Connection,
+    params: &SearchParams,
+) -> AppResult<SearchResult> {
+    let mut conditions: Vec<&str> = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+
+    // Every keyword term must appear in the name, description, shop or tags
+    for term in params.keyword.split_whitespace() {
+        conditions.push(
+            "(name LIKE ? ESCAPE '\\' OR description LIKE ? ESCAPE '\\'
+              OR shop_name LIKE ? ESCAPE '\\' OR tags_json LIKE ? ESCAPE '\\')",
+        );
+        let pattern = format!("%{}%", escape_like(term));
+        for _ in 0..4 {
+            values.push(Value::Text(pattern.clone()));
+        }
+    }
+    for tag in params
+        .tags
+        .iter()
+        .map(|t| t.trim())
+        .filter(|t| !t.is_empty())
+    {
+        conditions.push("tags_json LIKE ? ESCAPE '\\'");
+        let quoted = serde_json::to_string(tag).unwrap_or_default();
+        values.push(Value::Text(format!("%{}%", escape_like(&quoted))));
+    }
+    if let Some(category) = category_key(params.category.clone()) {
+        conditions.push("category_name = ?");
+        values.push(Value::Text(category));
+    }
+    if params.only_free.unwrap_or(false) {
+        conditions.push("price = 0");
+    } else {
+        if let Some(min) = params.price_min {
+            conditions.push("price >= ?");
+            values.push(Value::Integer(min));
+        }
+        if let Some(max) = params.price_max {
+            conditions.push("price <= ?");
+            values.push(Value::Integer(max));
+        }
+    }
+    let where_sql = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    // Booth ids grow over time, so id order stands in for listing date
+    let order_sql = match params.sort.as_deref() {
+        Some("new") => "id DESC",
+        Some("price_asc") => "price ASC, id DESC",
+        Some("price_desc") => "price DESC, id DESC",
+        _ => "COALESCE(wish_count, -1) DESC, id DESC",
+    };
+
+    let total_count: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM cached_items {}", where_sql),
+        params_from_iter(values.iter()),
+        |row| row.get(0),
+    )?;
+
+    let page = params.page.unwrap_or(1).max(1);
+    let offset = (page - 1) as i64 * OFFLINE_PAGE_SIZE as i64;
+    let sql = format!(
+        "SELECT {} FROM cached_items {} ORDER BY {} LIMIT {} OFFSET {}",
+        CACHED_ITEM_COLUMNS, where_sql, order_sql, OFFLINE_PAGE_SIZE, offset
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map(params_from_iter(values.iter()), |row| {
+            Ok((
+                cached_item_from_row(row)?,
+                row.get::<_, String>(CACHED_AT_INDEX)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let cached_at = rows.iter().map(|(_, at)| at.clone()).min();
+    Ok(SearchResult {
+        items: rows.into_iter().map(|(item, _)| item).collect(),
+        total_count: Some(total_count),
+        current_page: page,
+        offline: true,
+        cached_at,
+    })
+}
+
+const CACHED_ITEM_COLUMNS: &str = "id, name, description, price, category_name, shop_name, url,
+     images_json, tags_json, wish_count, locale, cached_at";
+const CACHED_AT_INDEX: usize = 11;
+
+/// Rebuild a `BoothItem` from a row selected with `CACHED_ITEM_COLUMNS`.
+/// Variations live in their own table and are left empty.
+pub(crate) fn cached_item_from_row(row: &Row) -> rusqlite::Result<BoothItem> {
+    let id: i64 = row.get(0)?;
+    let images_json: String = row.get(7)?;
+    let tags_json: String = row.get(8)?;
+    let locale: String = row.get(10)?;
+    Ok(BoothItem {
+        id,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        price: row.get(3)?,
+        category_name: row.get(4)?,
+        shop_name: row.get(5)?,
+        url: row.get(6)?,
+        images: serde_json::from_str(&images_json).unwrap_or_else(|e| {
+            log::warn!("Corrupt images_json for cached item {}: {}", id, e);
+            Vec::new()
+        }),
+        tags: serde_json::from_str(&tags_json).unwrap_or_else(|e| {
+            log::warn!("Corrupt tags_json for cached item {}: {}", id, e);
+            Vec::new()
+        }),
+        wish_lists_count: row.get(9)?,
+        variations: Vec::new(),
+        locale: Locale::from_code(&locale),
+    })
+}
+
+/// Detail data younger than this is served without contacting Booth
+const ITEM_FRESH_FOR: &str = "-1 hour";
+
+pub(crate) struct CachedItemRow {
+    pub item: BoothItem,
+    pub cached_at: String,
+    /// Full detail fetched within `ITEM_FRESH_FOR`; rows from search cards are never fresh
+    pub fresh: bool,
+}
+
+pub(crate) fn read_cached_item(
+    conn: &Connection,
+    item_id: i64,
+) -> AppResult<Option<CachedItemRow>> {
+    let sql = format!(
+        "SELECT {}, COALESCE(detail_cached_at > datetime('now', ?2), 0)
+         FROM cached_items WHERE id = ?1",
+        CACHED_ITEM_COLUMNS
+    );
+    let row = conn
+        .query_row(&sql, params![item_id, ITEM_FRESH_FOR], |row| {
+            Ok(CachedItemRow {
+                item: cached_item_from_row(row)?,
+                cached_at: row.get(CACHED_AT_INDEX)?,
+                fresh: row.get(CACHED_AT_INDEX + 1)?,
+            })
+        })
+        .optional()?;
+    let Some(mut row) = row else {
+        return Ok(None);
+    };
+
+    let mut stmt = conn.prepare(
+        "SELECT name, price, kind, stock FROM cached_item_variations
+         WHERE item_id = ?1 ORDER BY position",
+    )?;
+    row.item.variations = stmt
+        .query_map(params![item_id], |r| {
+            let kind: String = r.get(2)?;
+            let stock: String = r.get(3)?;
+            Ok(Variation {
+                name: r.get(0)?,
+                price: r.get(1)?,
+                kind: VariationKind::from_code(&kind),
+                stock: StockStatus::from_code(&stock),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Some(row))
+}
+
+pub(crate) fn escape_like(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Upsert shop metadata into `shops`.
+pub(crate) fn write_shop(conn: &Connection, shop: &ShopInfo) -> AppResult<()> {
+    conn.execute(
+        "INSERT INTO shops (subdomain, name, icon_url, description, item_count, cached_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, datetime('now'))
+         ON CONFLICT(subdomain) DO UPDATE SET
+            name = excluded.name,
+            icon_url = COALESCE(excluded.icon_url, shops.icon_url),
+            description = COALESCE(excluded.description, shops.description),
+            item_count = COALESCE(excluded.item_count, shops.item_count),
+            cached_at = excluded.cached_at",
+        params![
+            shop.subdomain,
+            shop.name,
+            shop.icon_url,
+            shop.description,
+            shop.item_count,
+        ],
+    )?;
+    Ok(())
+}
+
+pub(crate) fn read_cached_shop(conn: &Connection, subdomain: &str) -> AppResult<Option<ShopInfo>> {
+    let shop = conn
+        .query_row(
+            "SELECT subdomain, name, icon_url, description, item_count FROM shops WHERE subdomain = ?1",
+            params![subdomain],
+            |row| {
+                Ok(ShopInfo {
+                    subdomain: row.get(0)?,
+                    name: row.get(1)?,
+                    icon_url: row.get(2)?,
+                    description: row.get(3)?,
+                    item_count: row.get(4)?,
+                })
+            },
+        )
+        .optional()?;
+    Ok(shop)
+}
+
 #[tauri::command]
 pub fn save_search_history(db: State<'_, AppDatabase>, keyword: String) -> AppResult<()> {
     let keyword = keyword.trim().to_string();
@@ -100,35 +575,312 @@ pub fn save_search_history(db: State<'_, AppDatabase>, keyword: String) -> AppRe
 
 // ── Favorites ──────────────────────────────────────────
 
+/// Page size for `query_favorites` when the caller doesn't pass one
+const DEFAULT_FAVORITES_PAGE_SIZE: u32 = 100;
+const MAX_FAVORITES_PAGE_SIZE: u32 = 1000;
+/// Longest note accepted by `set_favorite_note`, in characters
+const MAX_NOTE_CHARS: usize = 2000;
+/// Longest variation name or order reference accepted by `mark_owned`
+const MAX_OWNERSHIP_FIELD_CHARS: usize = 200;
+
+/// Column list for `favorite_from_row`, read from `favorites f`.
+pub(crate) const FAVORITE_COLUMNS: &str =
+    "f.id, f.item_id, f.name, f.price, f.thumbnail_url, f.category_name, f.shop_name,
+     f.added_at, f.note, f.note_updated_at, f.refreshed_at, f.availability, f.last_checked_at,
+     f.owned, f.purchased_at, f.price_paid, f.purchased_variation, f.order_ref,
+     (SELECT ph.price FROM price_history ph WHERE ph.item_id = f.item_id
+      ORDER BY ph.observed_at DESC, ph.id DESC LIMIT 1),
+     (SELECT ph.price FROM price_history ph WHERE ph.item_id = f.item_id
+      ORDER BY ph.observed_at, ph.id LIMIT 1)";
+
+pub(crate) fn favorite_from_row(row: &Row) -> rusqlite::Result<FavoriteItem> {
+    let price: i64 = row.get(3)?;
+    let current_price: Option<i64> = row.get(18)?;
+    // Refreshes overwrite `price`; the first observation is the price when added
+    let added_price: i64 = row.get::<_, Option<i64>>(19)?.unwrap_or(price);
+    Ok(FavoriteItem {
+        id: row.get(0)?,
+        item_id: row.get(1)?,
+        name: row.get(2)?,
+        price,
+        thumbnail_url: row.get(4)?,
+        category_name: row.get(5)?,
+        shop_name: row.get(6)?,
+        added_at: row.get(7)?,
+        note: row.get(8)?,
+        note_updated_at: row.get(9)?,
+        refreshed_at: row.get(10)?,
+        availability: Availability::from_code(&row.get::<_, String>(11)?),
+        last_checked_at: row.get(12)?,
+        owned: row.get(13)?,
+        purchased_at: row.get(14)?,
+        price_paid: row.get(15)?,
+        purchased_variation: row.get(16)?,
+        order_ref: row.get(17)?,
+        current_price,
+        added_price,
+        price_dropped: current_price.is_some_and(|p| p < added_price),
+    })
+}
+
+/// SQL conditions on `favorites f` for `filter`, with their bound values.
+pub(crate) fn favorites_filter_sql(
+    filter: &FavoritesFilter,
+) -> AppResult<(Vec<String>, Vec<Value>)> {
+    let mut conditions: Vec<String> = Vec::new();
+    let mut values = Vec::new();
+    let like = |term: &str| Value::Text(format!("%{}%", escape_like(term)));
+
+    for term in filter
+        .text
+        .as_deref()
+        .unwrap_or_default()
+        .split_whitespace()
+    {
+        conditions.push(
+            "(f.name LIKE ? ESCAPE '\\' OR f.shop_name LIKE ? ESCAPE '\\'
+              OR f.category_name LIKE ? ESCAPE '\\' OR f.note LIKE ? ESCAPE '\\'
+              OR EXISTS (SELECT 1 FROM item_tags it
+                         WHERE it.item_id = f.item_id AND it.tag LIKE ? ESCAPE '\\'))"
+                .to_string(),
+        );
+        for _ in 0..5 {
+            values.push(like(term));
+        }
+    }
+    for term in filter
+        .note
+        .as_deref()
+        .unwrap_or_default()
+        .split_whitespace()
+    {
+        conditions.push("f.note LIKE ? ESCAPE '\\'".to_string());
+        values.push(like(term));
+    }
+
+    let mut tags: Vec<&str> = filter
+        .tags
+        .iter()
+        .map(|t| t.trim())
+        .filter(|t| !t.is_empty())
+        .collect();
+    tags.sort_unstable();
+    tags.dedup();
+    if !tags.is_empty() {
+        let placeholders = vec!["?"; tags.len()].join(", ");
+        conditions.push(match filter.tag_match {
+            TagMatch::Any => format!(
+                "EXISTS (SELECT 1 FROM item_tags it
+                         WHERE it.item_id = f.item_id AND it.tag IN ({}))",
+                placeholders
+            ),
+            TagMatch::All => format!(
+                "(SELECT COUNT(*) FROM item_tags it
+                  WHERE it.item_id = f.item_id AND it.tag IN ({})) = {}",
+                placeholders,
+                tags.len()
+            ),
+        });
+        values.extend(tags.iter().map(|t| Value::Text(t.to_string())));
+    }
+
+    if let Some(collection_id) = filter.collection_id {
+        conditions.push(
+            "EXISTS (SELECT 1 FROM collection_items ci
+                     WHERE ci.item_id = f.item_id AND ci.collection_id = ?)"
+                .to_string(),
+        );
+        values.push(Value::Integer(collection_id));
+    }
+    if let Some(min) = filter.price_min {
+        conditions.push("f.price >= ?".to_string());
+        values.push(Value::Integer(min));
+    }
+    if let Some(max) = filter.price_max {
+        conditions.push("f.price <= ?".to_string());
+        values.push(Value::Integer(max));
+    }
+    if let Some(shop) = filter
+        .shop
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        conditions.push("f.shop_name = ?".to_string());
+        values.push(Value::Text(shop.to_string()));
+    }
+    if let Some(category) = category_key(filter.category.clone()) {
+        conditions.push("f.category_name = ?".to_string());
+        values.push(Value::Text(category));
+    }
+    for (bound, sql) in [
+        (&filter.added_from, "f.added_at >= ?"),
+        (&filter.added_to, "f.added_at < date(?, '+1 day')"),
+    ] {
+        let Some(date) = bound.as_deref().map(str::trim).filter(|d| !d.is_empty()) else {
+            continue;
+        };
+        if !is_iso_date(date) {
+            return Err(AppError::Validation(format!(
+                "Invalid date: {} (expected YYYY-MM-DD)",
+                date
+            )));
+        }
+        conditions.push(sql.to_string());
+        values.push(Value::Text(date.to_string()));
+    }
+
+    if let Some(availability) = filter.availability {
+        conditions.push("f.availability = ?".to_string());
+        values.push(Value::Text(availability.as_str().to_string()));
+    }
+    if let Some(owned) = filter.owned {
+        conditions.push("f.owned = ?".to_string());
+        values.push(Value::Integer(owned.into()));
+    }
+    Ok((conditions, values))
+}
+
 #[tauri::command]
-pub fn get_favorites(db: State<'_, AppDatabase>) -> AppResult<Vec<FavoriteItem>> {
-    let conn = db.conn()?;
-    let mut stmt = conn.prepare(
-        "SELECT id, item_id, name, price, thumbnail_url, category_name, shop_name, added_at, note
-         FROM favorites ORDER BY added_at DESC",
-    )?;
+pub fn get_favorites(
+    db: State<'_, AppDatabase>,
+    filter: Option<FavoritesFilter>,
+) -> AppResult<Vec<FavoriteItem>> {
+    read_favorites(&*db.conn()?, &filter.unwrap_or_default())
+}
+
+pub(crate) fn read_favorites(
+    conn: &Connection,
+    filter: &FavoritesFilter,
+) -> AppResult<Vec<FavoriteItem>> {
+    let (where_sql, values) = favorites_where(filter)?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM favorites f {} ORDER BY f.added_at DESC",
+        FAVORITE_COLUMNS, where_sql
+    ))?;
     let rows = stmt
-        .query_map([], |row| {
-            Ok(FavoriteItem {
-                id: row.get(0)?,
-                item_id: row.get(1)?,
-                name: row.get(2)?,
-                price: row.get(3)?,
-                thumbnail_url: row.get(4)?,
-                category_name: row.get(5)?,
-                shop_name: row.get(6)?,
-                added_at: row.get(7)?,
-                note: row.get(8)?,
-            })
-        })?
+        .query_map(params_from_iter(values.iter()), favorite_from_row)?
         .collect::<Result<Vec<_>, _>>()?;
     Ok(rows)
 }
 
+/// One page of favorites matching `filter`, with each item's tags and
+/// collections, so the UI doesn't have to filter the whole library itself.
+#[tauri::command]
+pub fn query_favorites(
+    db: State<'_, AppDatabase>,
+    filter: Option<FavoritesFilter>,
+    sort: Option<FavoritesSort>,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> AppResult<FavoritesPage> {
+    read_favorites_page(
+        &*db.conn()?,
+        &filter.unwrap_or_default(),
+        sort.unwrap_or_default(),
+        offset.unwrap_or(0),
+        limit.unwrap_or(DEFAULT_FAVORITES_PAGE_SIZE),
+    )
+}
+
+pub(crate) fn read_favorites_page(
+    conn: &Connection,
+    filter: &FavoritesFilter,
+    sort: FavoritesSort,
+    offset: u32,
+    limit: u32,
+) -> AppResult<FavoritesPage> {
+    let limit = limit.clamp(1, MAX_FAVORITES_PAGE_SIZE);
+    let (where_sql, values) = favorites_where(filter)?;
+
+    let total_count: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM favorites f {}", where_sql),
+        params_from_iter(values.iter()),
+        |row| row.get(0),
+    )?;
+
+    let order_sql = match sort {
+        FavoritesSort::AddedDesc => "f.added_at DESC, f.id DESC",
+        FavoritesSort::AddedAsc => "f.added_at ASC, f.id ASC",
+        FavoritesSort::PriceAsc => "f.price ASC, f.id DESC",
+        FavoritesSort::PriceDesc => "f.price DESC, f.id DESC",
+        FavoritesSort::Name => "f.name COLLATE NOCASE ASC, f.id DESC",
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM favorites f {} ORDER BY {} LIMIT {} OFFSET {}",
+        FAVORITE_COLUMNS, where_sql, order_sql, limit, offset
+    ))?;
+    let favorites = stmt
+        .query_map(params_from_iter(values.iter()), favorite_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Tags and collections for this page only
+    let ids: Vec<Value> = favorites
+        .iter()
+        .map(|f| Value::Integer(f.item_id))
+        .collect();
+    let mut tags: HashMap<i64, Vec<String>> = HashMap::new();
+    let mut collections: HashMap<i64, Vec<i64>> = HashMap::new();
+    if !ids.is_empty() {
+        let placeholders = vec!["?"; ids.len()].join(", ");
+        let mut stmt = conn.prepare(&format!(
+            "SELECT item_id, tag FROM item_tags WHERE item_id IN ({}) ORDER BY tag",
+            placeholders
+        ))?;
+        let mut rows = stmt.query(params_from_iter(ids.iter()))?;
+        while let Some(row) = rows.next()? {
+            tags.entry(row.get(0)?).or_default().push(row.get(1)?);
+        }
+        let mut stmt = conn.prepare(&format!(
+            "SELECT item_id, collection_id FROM collection_items
+             WHERE item_id IN ({}) ORDER BY collection_id",
+            placeholders
+        ))?;
+        let mut rows = stmt.query(params_from_iter(ids.iter()))?;
+        while let Some(row) = rows.next()? {
+            collections
+                .entry(row.get(0)?)
+                .or_default()
+                .push(row.get(1)?);
+        }
+    }
+
+    let items = favorites
+        .into_iter()
+        .map(|item| FavoriteEntry {
+            tags: tags.remove(&item.item_id).unwrap_or_default(),
+            collection_ids: collections.remove(&item.item_id).unwrap_or_default(),
+            item,
+        })
+        .collect();
+    Ok(FavoritesPage {
+        items,
+        total_count,
+        offset,
+        limit,
+    })
+}
+
+/// `WHERE` clause (empty when unfiltered) and bound values for `filter`.
+fn favorites_where(filter: &FavoritesFilter) -> AppResult<(String, Vec<Value>)> {
+    let (conditions, values) = favorites_filter_sql(filter)?;
+    let where_sql = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+    Ok((where_sql, values))
+}
+
 #[tauri::command]
 pub fn add_favorite(db: State<'_, AppDatabase>, params: AddFavoriteParams) -> AppResult<()> {
-    let conn = db.conn()?;
-    conn.execute(
+    insert_favorite(&*db.conn()?, &params)?;
+    Ok(())
+}
+
+/// Add a favorite unless it already is one; returns whether it was added.
+pub(crate) fn insert_favorite(conn: &Connection, params: &AddFavoriteParams) -> AppResult<bool> {
+    let added = conn.execute(
         "INSERT OR IGNORE INTO favorites
          (item_id, name, price, thumbnail_url, category_name, shop_name, added_at)
          VALUES (?1, ?2, ?3, ?4, ?5, ?6, datetime('now'))",
@@ -137,27 +889,192 @@ pub fn add_favorite(db: State<'_, AppDatabase>, params: AddFavoriteParams) -> Ap
             params.name,
             params.price,
             params.thumbnail_url,
-            params.category_name,
+            category_key(params.category_name.clone()),
             params.shop_name,
         ],
     )?;
-    Ok(())
+    record_price(conn, params.item_id, params.price)?;
+    Ok(added > 0)
 }
 
 #[tauri::command]
 pub fn remove_favorite(db: State<'_, AppDatabase>, item_id: i64) -> AppResult<()> {
     let mut conn = db.conn_mut()?;
     let tx = conn.transaction()?;
-    tx.execute(
+    delete_favorite(&tx, item_id)?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Delete a favorite with its tags, collection memberships and history;
+/// returns whether it was a favorite. Run inside a transaction.
+pub(crate) fn delete_favorite(conn: &Connection, item_id: i64) -> AppResult<bool> {
+    conn.execute(
         "DELETE FROM collection_items WHERE item_id = ?1",
         params![item_id],
     )?;
-    tx.execute("DELETE FROM item_tags WHERE item_id = ?1", params![item_id])?;
-    tx.execute("DELETE FROM favorites WHERE item_id = ?1", params![item_id])?;
-    tx.commit()?;
+    conn.execute("DELETE FROM item_tags WHERE item_id = ?1", params![item_id])?;
+    conn.execute(
+        "DELETE FROM price_history WHERE item_id = ?1",
+        params![item_id],
+    )?;
+    conn.execute(
+        "DELETE FROM favorite_changes WHERE item_id = ?1",
+        params![item_id],
+    )?;
+    let removed = conn.execute("DELETE FROM favorites WHERE item_id = ?1", params![item_id])?;
+    Ok(removed > 0)
+}
+
+/// Prices observed for a favorite, oldest first. A point is only added when the
+/// price changes, so each one marks the start of a new price.
+#[tauri::command]
+pub fn get_price_history(db: State<'_, AppDatabase>, item_id: i64) -> AppResult<Vec<PricePoint>> {
+    read_price_history(&*db.conn()?, item_id)
+}
+
+fn read_price_history(conn: &Connection, item_id: i64) -> AppResult<Vec<PricePoint>> {
+    let mut stmt = conn.prepare(
+        "SELECT price, observed_at FROM price_history
+         WHERE item_id = ?1 ORDER BY observed_at, id",
+    )?;
+    let rows = stmt
+        .query_map(params![item_id], |row| {
+            Ok(PricePoint {
+                price: row.get(0)?,
+                observed_at: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Set or clear (empty/whitespace `note`) the personal note on a favorite.
+#[tauri::command]
+pub fn set_favorite_note(
+    db: State<'_, AppDatabase>,
+    item_id: i64,
+    note: Option<String>,
+) -> AppResult<()> {
+    write_favorite_note(&*db.conn()?, item_id, note.as_deref())
+}
+
+fn write_favorite_note(conn: &Connection, item_id: i64, note: Option<&str>) -> AppResult<()> {
+    let note = note.map(str::trim).filter(|n| !n.is_empty());
+    if let Some(note) = note {
+        if note.chars().count() > MAX_NOTE_CHARS {
+            return Err(AppError::Validation(format!(
+                "Note too long (max {} characters)",
+                MAX_NOTE_CHARS
+            )));
+        }
+    }
+    let updated = conn.execute(
+        "UPDATE favorites SET note = ?2, note_updated_at = datetime('now') WHERE item_id = ?1",
+        params![item_id, note],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!("Favorite {}", item_id)));
+    }
     Ok(())
 }
 
+/// Mark a favorite as bought. Calling it again replaces the purchase details.
+#[tauri::command]
+pub fn mark_owned(
+    db: State<'_, AppDatabase>,
+    item_id: i64,
+    params: Option<OwnershipParams>,
+) -> AppResult<()> {
+    write_ownership(&*db.conn()?, item_id, &params.unwrap_or_default())
+}
+
+/// Move a favorite back to the wishlist, forgetting its purchase details.
+#[tauri::command]
+pub fn unmark_owned(db: State<'_, AppDatabase>, item_id: i64) -> AppResult<()> {
+    clear_ownership(&*db.conn()?, item_id)
+}
+
+fn write_ownership(conn: &Connection, item_id: i64, params: &OwnershipParams) -> AppResult<()> {
+    let purchased_at = params
+        .purchased_at
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty());
+    if let Some(date) = purchased_at {
+        if !is_iso_date(date) {
+            return Err(AppError::Validation(format!(
+                "Invalid purchase date: {} (expected YYYY-MM-DD)",
+                date
+            )));
+        }
+    }
+    if params.price_paid.is_some_and(|p| p < 0) {
+        return Err(AppError::Validation(
+            "Price paid cannot be negative".to_string(),
+        ));
+    }
+    let variation = ownership_field("Variation", params.variation.as_deref())?;
+    let order_ref = ownership_field("Order reference", params.order_ref.as_deref())?;
+
+    let updated = conn.execute(
+        "UPDATE favorites SET owned = 1, purchased_at = COALESCE(?2, date('now')),
+             price_paid = ?3, purchased_variation = ?4, order_ref = ?5
+         WHERE item_id = ?1",
+        params![
+            item_id,
+            purchased_at,
+            params.price_paid,
+            variation,
+            order_ref
+        ],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!("Favorite {}", item_id)));
+    }
+    Ok(())
+}
+
+fn clear_ownership(conn: &Connection, item_id: i64) -> AppResult<()> {
+    let updated = conn.execute(
+        "UPDATE favorites SET owned = 0, purchased_at = NULL, price_paid = NULL,
+             purchased_variation = NULL, order_ref = NULL
+         WHERE item_id = ?1",
+        params![item_id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!("Favorite {}", item_id)));
+    }
+    Ok(())
+}
+
+/// Trimmed `value`, `None` when blank; too-long values are rejected.
+fn ownership_field<'a>(label: &str, value: Option<&'a str>) -> AppResult<Option<&'a str>> {
+    let value = value.map(str::trim).filter(|v| !v.is_empty());
+    if value.is_some_and(|v| v.chars().count() > MAX_OWNERSHIP_FIELD_CHARS) {
+        return Err(AppError::Validation(format!(
+            "{} too long (max {} characters)",
+            label, MAX_OWNERSHIP_FIELD_CHARS
+        )));
+    }
+    Ok(value)
+}
+
+/// `YYYY-MM-DD` with a plausible month and day; SQLite's date functions take it as is.
+fn is_iso_date(s: &str) -> bool {
+    let shaped = s.len() == 10
+        && s.bytes().enumerate().all(|(i, b)| match i {
+            4 | 7 => b == b'-',
+            _ => b.is_ascii_digit(),
+        });
+    if !shaped {
+        return false;
+    }
+    let month: u32 = s[5..7].parse().unwrap_or(0);
+    let day: u32 = s[8..10].parse().unwrap_or(0);
+    (1..=12).contains(&month) && (1..=31).contains(&day)
+}
+
 // ── Popular Avatars ────────────────────────────────────
 
 #[tauri::command]
@@ -218,3 +1135,561 @@ pub fn update_popular_avatar(
     )?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: i64, name: &str, price: i64, wish: Option<i64>) -> BoothItem {
+        BoothItem {
+            id,
+            name: name.to_string(),
+            description: None,
+            price,
+            category_name: Some("3D衣装".to_string()),
+            shop_name: Some("Kipfel Shop".to_string()),
+            url: format!("https://booth.pm/ja/items/{}", id),
+            images: vec![format!("https://booth.pximg.net/{}.jpg", id)],
+            tags: vec!["VRChat".to_string()],
+            wish_lists_count: wish,
+            variations: Vec::new(),
+            locale: Locale::Ja,
+        }
+    }
+
+    fn seeded() -> AppDatabase {
+        let db = AppDatabase::open_in_memory().unwrap();
+        let mut items = vec![
+            item(1, "ワンピース 夏", 1500, Some(10)),
+            item(2, "ワンピース 冬", 3000, Some(300)),
+            item(3, "100%_free hair", 0, None),
+        ];
+        items[2].category_name = Some("3D Props".to_string());
+        items[2].tags = vec!["Free".to_string()];
+        write_cached_items(&mut db.conn_mut().unwrap(), &items, false).unwrap();
+        db
+    }
+
+    fn search(db: &AppDatabase, params: SearchParams) -> SearchResult {
+        query_cached_items(&db.conn().unwrap(), &params).unwrap()
+    }
+
+    fn ids(result: &SearchResult) -> Vec<i64> {
+        result.items.iter().map(|i| i.id).collect()
+    }
+
+    #[test]
+    fn offline_search_matches_every_keyword_term() {
+        let db = seeded();
+        let result = search(
+            &db,
+            SearchParams {
+                keyword: "ワンピース 冬".into(),
+                ..Default::default()
+            },
+        );
+        assert_eq!(ids(&result), vec![2]);
+        assert!(result.offline);
+        assert!(result.cached_at.is_some());
+        assert_eq!(result.total_count, Some(1));
+        assert_eq!(result.items[0].tags, vec!["VRChat"]);
+    }
+
+    #[test]
+    fn offline_search_escapes_like_wildcards() {
+        let db = seeded();
+        let by_percent = SearchParams {
+            keyword: "100%_".into(),
+            ..Default::default()
+        };
+        assert_eq!(ids(&search(&db, by_percent)), vec![3]);
+        let wildcard = SearchParams {
+            keyword: "%".into(),
+            ..Default::default()
+        };
+        assert_eq!(ids(&search(&db, wildcard)), vec![3]);
+    }
+
+    #[test]
+    fn offline_search_filters_and_sorts() {
+        let db = seeded();
+        // Default order is by wish count, unknown counts last
+        assert_eq!(ids(&search(&db, SearchParams::default())), vec![2, 1, 3]);
+
+        let cheap_clothing = SearchParams {
+            category: Some("3D Clothing".into()),
+            price_max: Some(2000),
+            ..Default::default()
+        };
+        assert_eq!(ids(&search(&db, cheap_clothing)), vec![1]);
+
+        let free = SearchParams {
+            only_free: Some(true),
+            tags: vec!["Free".into()],
+            ..Default::default()
+        };
+        assert_eq!(ids(&search(&db, free)), vec![3]);
+
+        let by_price = SearchParams {
+            sort: Some("price_desc".into()),
+            ..Default::default()
+        };
+        assert_eq!(ids(&search(&db, by_price)), vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn offline_search_pages_past_the_end_are_empty() {
+        let db = seeded();
+        let result = search(
+            &db,
+            SearchParams {
+                page: Some(2),
+                ..Default::default()
+            },
+        );
+        assert!(result.items.is_empty());
+        assert_eq!(result.total_count, Some(3));
+        assert!(result.cached_at.is_none());
+    }
+
+    #[test]
+    fn card_rows_are_never_fresh() {
+        let db = seeded();
+        let row = read_cached_item(&db.conn().unwrap(), 1).unwrap().unwrap();
+        assert_eq!(row.item.name, "ワンピース 夏");
+        assert!(!row.fresh);
+        assert!(read_cached_item(&db.conn().unwrap(), 999)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn detail_rows_are_fresh_until_they_age_out() {
+        let db = seeded();
+        let mut detail = item(1, "ワンピース 夏", 1500, Some(12));
+        detail.description = Some("説明".into());
+        detail.variations = vec![Variation {
+            name: Some("支援版".into()),
+            price: 3000,
+            kind: VariationKind::Digital,
+            stock: StockStatus::Available,
+        }];
+        write_cached_items(&mut db.conn_mut().unwrap(), &[detail], true).unwrap();
+
+        let row = read_cached_item(&db.conn().unwrap(), 1).unwrap().unwrap();
+        assert!(row.fresh);
+        assert_eq!(row.item.variations.len(), 1);
+        assert_eq!(row.item.variations[0].price, 3000);
+
+        db.conn()
+            .unwrap()
+            .execute(
+                "UPDATE cached_items SET detail_cached_at = datetime('now', '-2 hours') WHERE id = 1",
+                [],
+            )
+            .unwrap();
+        assert!(
+            !read_cached_item(&db.conn().unwrap(), 1)
+                .unwrap()
+                .unwrap()
+                .fresh
+        );
+    }
+
+    #[test]
+    fn card_write_keeps_detail_fields() {
+        let db = seeded();
+        let mut detail = item(2, "ワンピース 冬", 3000, None);
+        detail.description = Some("冬用".into());
+        detail.tags = vec!["VRChat".into(), "冬".into()];
+        write_cached_items(&mut db.conn_mut().unwrap(), &[detail], true).unwrap();
+
+        // A later search card with a new price but no description or tags
+        let mut card = item(2, "ワンピース 冬", 2500, None);
+        card.tags = Vec::new();
+        write_cached_items(&mut db.conn_mut().unwrap(), &[card], false).unwrap();
+
+        let row = read_cached_item(&db.conn().unwrap(), 2).unwrap().unwrap();
+        assert_eq!(row.item.price, 2500);
+        assert_eq!(row.item.description.as_deref(), Some("冬用"));
+        assert_eq!(row.item.tags, vec!["VRChat", "冬"]);
+        // Wish count from the first write survives too
+        assert_eq!(row.item.wish_lists_count, Some(300));
+        assert!(row.fresh);
+    }
+
+    /// Favorite at ¥1000, recorded in the price history like `add_favorite` does
+    fn favorite(db: &AppDatabase, item_id: i64) {
+        let conn = db.conn().unwrap();
+        conn.execute(
+            "INSERT INTO favorites (item_id, name, price) VALUES (?1, 'fav', 1000)",
+            params![item_id],
+        )
+        .unwrap();
+        record_price(&conn, item_id, 1000).unwrap();
+    }
+
+    fn notes(db: &AppDatabase, note: &str) -> Vec<i64> {
+        let filter = FavoritesFilter {
+            note: Some(note.to_string()),
+            ..Default::default()
+        };
+        read_favorites(&db.conn().unwrap(), &filter)
+            .unwrap()
+            .iter()
+            .map(|f| f.item_id)
+            .collect()
+    }
+
+    #[test]
+    fn favorite_notes_are_saved_and_cleared() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        favorite(&db, 1);
+        let conn = db.conn().unwrap();
+
+        write_favorite_note(&conn, 1, Some("  bought for Kipfel  ")).unwrap();
+        let fav = &read_favorites(&conn, &FavoritesFilter::default()).unwrap()[0];
+        assert_eq!(fav.note.as_deref(), Some("bought for Kipfel"));
+        assert!(fav.note_updated_at.is_some());
+
+        write_favorite_note(&conn, 1, Some("   ")).unwrap();
+        let fav = &read_favorites(&conn, &FavoritesFilter::default()).unwrap()[0];
+        assert!(fav.note.is_none());
+    }
+
+    #[test]
+    fn favorite_note_validation() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        favorite(&db, 1);
+        let conn = db.conn().unwrap();
+
+        // Counted in characters, not bytes
+        let max = "あ".repeat(MAX_NOTE_CHARS);
+        assert!(write_favorite_note(&conn, 1, Some(&max)).is_ok());
+        let too_long = "a".repeat(MAX_NOTE_CHARS + 1);
+        assert!(matches!(
+            write_favorite_note(&conn, 1, Some(&too_long)),
+            Err(AppError::Validation(_))
+        ));
+        assert!(matches!(
+            write_favorite_note(&conn, 2, Some("not a favorite")),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn favorites_filter_by_note_terms() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        for id in 1..=3 {
+            favorite(&db, id);
+        }
+        {
+            let conn = db.conn().unwrap();
+            write_favorite_note(
+                &conn,
+                1,
+                Some("bought for Kipfel, needs the lilToon 1.7 fix"),
+            )
+            .unwrap();
+            write_favorite_note(&conn, 2, Some("for Manuka")).unwrap();
+            write_favorite_note(&conn, 3, Some("50% off")).unwrap();
+        }
+
+        assert_eq!(notes(&db, "kipfel"), vec![1]);
+        assert_eq!(notes(&db, "for liltoon"), vec![1]);
+        assert_eq!(notes(&db, "%"), vec![3]);
+        assert_eq!(notes(&db, "  ").len(), 3);
+    }
+
+    #[test]
+    fn favorites_filter_by_availability() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        for id in 1..=3 {
+            favorite(&db, id);
+        }
+        let conn = db.conn().unwrap();
+        record_availability(&conn, 1, Availability::Available).unwrap();
+        record_availability(&conn, 2, Availability::Private).unwrap();
+
+        let matching = |availability| {
+            let filter = FavoritesFilter {
+                availability: Some(availability),
+                ..Default::default()
+            };
+            read_favorites(&conn, &filter)
+                .unwrap()
+                .iter()
+                .map(|f| (f.item_id, f.last_checked_at.is_some()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(matching(Availability::Private), vec![(2, true)]);
+        assert_eq!(matching(Availability::Unknown), vec![(3, false)]);
+        assert!(matching(Availability::NotFound).is_empty());
+        assert!(Availability::from_fetch(&Err::<(), _>(AppError::Network("x".into()))).is_none());
+    }
+
+    /// Favorites 1-4 at ¥1000-¥4000 in one shop, added a day apart, with tags
+    /// and a collection holding 2 and 3
+    fn library(db: &AppDatabase) {
+        let conn = db.conn().unwrap();
+        conn.execute_batch(
+            "INSERT INTO favorites (item_id, name, price, shop_name, added_at) VALUES
+                (1, 'Kipfel outfit', 1000, 'Shop A', '2024-05-01 10:00:00'),
+                (2, 'Manuka hair', 2000, 'Shop A', '2024-05-02 10:00:00'),
+                (3, 'lilToon shader', 3000, 'Shop B', '2024-05-03 10:00:00'),
+                (4, 'Boots', 4000, 'Shop B', '2024-05-04 10:00:00');
+             INSERT INTO item_tags (item_id, tag) VALUES
+                (1, 'kipfel'), (1, 'outfit'), (2, 'manuka'), (3, 'kipfel'), (3, 'manuka');
+             INSERT INTO collections (id, name) VALUES (7, 'wip');
+             INSERT INTO collection_items (collection_id, item_id) VALUES (7, 2), (7, 3);",
+        )
+        .unwrap();
+    }
+
+    fn page_ids(db: &AppDatabase, filter: FavoritesFilter, sort: FavoritesSort) -> Vec<i64> {
+        read_favorites_page(&db.conn().unwrap(), &filter, sort, 0, 100)
+            .unwrap()
+            .items
+            .iter()
+            .map(|e| e.item.item_id)
+            .collect()
+    }
+
+    #[test]
+    fn query_favorites_filters() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        library(&db);
+        let ids = |filter| page_ids(&db, filter, FavoritesSort::AddedAsc);
+
+        let tags = |tags: &[&str], tag_match| FavoritesFilter {
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            tag_match,
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(tags(&["kipfel", "manuka"], TagMatch::Any)),
+            vec![1, 2, 3]
+        );
+        assert_eq!(ids(tags(&["kipfel", "manuka"], TagMatch::All)), vec![3]);
+        assert_eq!(ids(tags(&["kipfel", "kipfel"], TagMatch::All)), vec![1, 3]);
+
+        // Text matches names and tags alike
+        let text = |text: &str| FavoritesFilter {
+            text: Some(text.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(ids(text("manuka")), vec![2, 3]);
+        assert_eq!(ids(text("kipfel outfit")), vec![1]);
+
+        assert_eq!(
+            ids(FavoritesFilter {
+                collection_id: Some(7),
+                price_min: Some(2500),
+                ..Default::default()
+            }),
+            vec![3]
+        );
+        assert_eq!(
+            ids(FavoritesFilter {
+                shop: Some("Shop A".to_string()),
+                price_max: Some(1500),
+                ..Default::default()
+            }),
+            vec![1]
+        );
+        assert_eq!(
+            ids(FavoritesFilter {
+                added_from: Some("2024-05-02".to_string()),
+                added_to: Some("2024-05-03".to_string()),
+                ..Default::default()
+            }),
+            vec![2, 3]
+        );
+
+        let bad_date = FavoritesFilter {
+            added_to: Some("May 3".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            read_favorites_page(&db.conn().unwrap(), &bad_date, FavoritesSort::Name, 0, 10),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn query_favorites_sorts_pages_and_attaches_tags() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        library(&db);
+
+        assert_eq!(
+            page_ids(&db, FavoritesFilter::default(), FavoritesSort::AddedDesc),
+            vec![4, 3, 2, 1]
+        );
+        assert_eq!(
+            page_ids(&db, FavoritesFilter::default(), FavoritesSort::Name),
+            vec![4, 1, 3, 2]
+        );
+
+        let conn = db.conn().unwrap();
+        let page = read_favorites_page(
+            &conn,
+            &FavoritesFilter::default(),
+            FavoritesSort::PriceDesc,
+            1,
+            2,
+        )
+        .unwrap();
+        assert_eq!(page.total_count, 4);
+        let entries: Vec<_> = page
+            .items
+            .iter()
+            .map(|e| (e.item.item_id, e.tags.clone(), e.collection_ids.clone()))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                (3, vec!["kipfel".to_string(), "manuka".to_string()], vec![7]),
+                (2, vec!["manuka".to_string()], vec![7]),
+            ]
+        );
+    }
+
+    #[test]
+    fn ownership_is_marked_and_cleared() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        favorite(&db, 1);
+        favorite(&db, 2);
+        let conn = db.conn().unwrap();
+
+        let params = OwnershipParams {
+            purchased_at: Some("2024-03-09".to_string()),
+            price_paid: Some(800),
+            variation: Some("  Full set ".to_string()),
+            order_ref: Some("".to_string()),
+        };
+        write_ownership(&conn, 1, &params).unwrap();
+        write_ownership(&conn, 2, &OwnershipParams::default()).unwrap();
+
+        let owned = FavoritesFilter {
+            owned: Some(true),
+            ..Default::default()
+        };
+        let favs = read_favorites(&conn, &owned).unwrap();
+        let fav = favs.iter().find(|f| f.item_id == 1).unwrap();
+        assert_eq!(fav.purchased_at.as_deref(), Some("2024-03-09"));
+        assert_eq!(fav.price_paid, Some(800));
+        assert_eq!(fav.purchased_variation.as_deref(), Some("Full set"));
+        assert!(fav.order_ref.is_none());
+        // No date given: today
+        let fav = favs.iter().find(|f| f.item_id == 2).unwrap();
+        assert_eq!(fav.purchased_at.as_ref().map(|d| d.len()), Some(10));
+
+        clear_ownership(&conn, 1).unwrap();
+        let wishlist = FavoritesFilter {
+            owned: Some(false),
+            ..Default::default()
+        };
+        let favs = read_favorites(&conn, &wishlist).unwrap();
+        assert_eq!(favs.len(), 1);
+        assert!(favs[0].purchased_at.is_none() && favs[0].price_paid.is_none());
+    }
+
+    #[test]
+    fn ownership_validation() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        favorite(&db, 1);
+        let conn = db.conn().unwrap();
+
+        for date in ["2024-13-01", "2024-1-01", "yesterday"] {
+            let params = OwnershipParams {
+                purchased_at: Some(date.to_string()),
+                ..Default::default()
+            };
+            assert!(
+                matches!(
+                    write_ownership(&conn, 1, &params),
+                    Err(AppError::Validation(_))
+                ),
+                "{}",
+                date
+            );
+        }
+        let params = OwnershipParams {
+            price_paid: Some(-1),
+            ..Default::default()
+        };
+        assert!(matches!(
+            write_ownership(&conn, 1, &params),
+            Err(AppError::Validation(_))
+        ));
+        assert!(matches!(
+            write_ownership(&conn, 2, &OwnershipParams::default()),
+            Err(AppError::NotFound(_))
+        ));
+        assert!(matches!(
+            clear_ownership(&conn, 2),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn price_history_tracks_favorite_price_changes() {
+        let db = seeded();
+        favorite(&db, 2);
+
+        // Seen at a new price, again at the same price, then on sale, then back up
+        for price in [3000, 3000, 2400, 2400, 3000] {
+            write_cached_items(
+                &mut db.conn_mut().unwrap(),
+                &[item(2, "ワンピース 冬", price, None)],
+                false,
+            )
+            .unwrap();
+        }
+        // Not a favorite: nothing recorded
+        write_cached_items(
+            &mut db.conn_mut().unwrap(),
+            &[item(1, "ワンピース 夏", 900, None)],
+            false,
+        )
+        .unwrap();
+
+        let conn = db.conn().unwrap();
+        let prices: Vec<i64> = read_price_history(&conn, 2)
+            .unwrap()
+            .iter()
+            .map(|p| p.price)
+            .collect();
+        assert_eq!(prices, vec![1000, 3000, 2400, 3000]);
+        assert!(read_price_history(&conn, 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn favorites_flag_price_drops() {
+        let db = seeded();
+        favorite(&db, 1);
+        favorite(&db, 2);
+        write_cached_items(
+            &mut db.conn_mut().unwrap(),
+            &[
+                item(1, "ワンピース 夏", 800, None),
+                item(2, "ワンピース 冬", 1200, None),
+            ],
+            false,
+        )
+        .unwrap();
+
+        // Both were favorited at 1000; a refresh overwriting `price` doesn't hide the drop
+        db.conn()
+            .unwrap()
+            .execute("UPDATE favorites SET price = 800 WHERE item_id = 1", [])
+            .unwrap();
+        let favs = read_favorites(&db.conn().unwrap(), &FavoritesFilter::default()).unwrap();
+        let by_id = |id: i64| favs.iter().find(|f| f.item_id == id).unwrap();
+        assert_eq!(by_id(1).current_price, Some(800));
+        assert_eq!(by_id(1).added_price, 1000);
+        assert!(by_id(1).price_dropped);
+        assert_eq!(by_id(2).current_price, Some(1200));
+        assert!(!by_id(2).price_dropped);
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use rusqlite::params_from_iter;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::booth::client::BoothClient;
+use crate::booth::models::{BoothItem, Locale};
+use crate::booth::scheduler::Priority;
+use crate::database::AppDatabase;
+use crate::error::{AppError, AppResult};
+
+use super::db::write_cached_items;
+
+/// Wish counts younger than this are served from `cached_items`.
+const WISH_COUNT_MAX_AGE: &str = "-1 day";
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+/// The newest job; older ones stop before their next fetch, since their
+/// results belong to a search the user has already left.
+static CURRENT_JOB: AtomicU64 = AtomicU64::new(0);
+
+// ── Types ──────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct WishCountJob {
+    pub job_id: u64,
+    /// Fresh counts already in the cache, keyed by item id
+    pub cached: HashMap<i64, i64>,
+    /// Items that will be fetched and reported through `wish-count-progress`
+    pub pending: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WishCountProgress {
+    pub job_id: u64,
+    pub item_id: i64,
+    /// `None` when the fetch failed
+    pub wish_count: Option<i64>,
+    pub completed: usize,
+    pub total: usize,
+}
+
+/// Emitted as `wish-count-done` once, however the job ends.
+#[derive(Debug, Clone, Serialize)]
+pub struct WishCountDone {
+    pub job_id: u64,
+    pub completed: usize,
+    pub total: usize,
+    /// `Cancelled` when a newer job replaced this one
+    pub error: Option<AppError>,
+}
+
+// ── Commands ───────────────────────────────────────────
+
+/// Start fetching wish counts for the given items in the background.
+///
+/// Counts that are still fresh in the cache are returned immediately; the rest
+/// are fetched at `Enrich` priority and written to `cached_items`, with one
+/// `wish-count-progress` event per item and a final `wish-count-done`.
+/// Starting a job stops the previous one.
+#[tauri::command]
+pub fn start_wish_count_job(
+    app: AppHandle,
+    db: State<'_, AppDatabase>,
+    item_ids: Vec<i64>,
+    locale: Option<Locale>,
+) -> AppResult<WishCountJob> {
+    let (cached, pending) = split_fresh(&db, item_ids)?;
+    let job_id = NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed);
+    CURRENT_JOB.store(job_id, Ordering::Release);
+
+    let job = WishCountJob {
+        job_id,
+        cached,
+        pending: pending.len(),
+    };
+    if !pending.is_empty() {
+        tauri::async_runtime::spawn(run_wish_count_job(
+            app,
+            job_id,
+            pending,
+            locale.unwrap_or_default(),
+        ));
+    }
+    Ok(job)
+}
+
+/// Fresh cached counts, and the remaining (missing or stale) ids to fetch.
+fn split_fresh(db: &AppDatabase, item_ids: Vec<i64>) -> AppResult<(HashMap<i64, i64>, Vec<i64>)> {
+    let mut ids = item_ids;
+    ids.sort_unstable();
+    ids.dedup();
+
+    let cached = fresh_wish_counts(db, &ids)?;
+    let pending = ids
+        .into_iter()
+        .filter(|id| !cached.contains_key(id))
+        .collect();
+    Ok((cached, pending))
+}
+
+fn fresh_wish_counts(db: &AppDatabase, ids: &[i64]) -> AppResult<HashMap<i64, i64>> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let conn = db.conn()?;
+    let placeholders = vec!["?"; ids.len()].join(",");
+    let sql = format!(
+        "SELECT id, wish_count FROM cached_items
+         WHERE id IN ({}) AND wish_count IS NOT NULL
+           AND wish_count_at > datetime('now', '{}')",
+        placeholders, WISH_COUNT_MAX_AGE
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map(params_from_iter(ids), |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<HashMap<i64, i64>, _>>()?;
+    Ok(rows)
+}
+
+async fn run_wish_count_job(app: AppHandle, job_id: u64, item_ids: Vec<i64>, locale: Locale) {
+    let client = app.state::<BoothClient>();
+    let db = app.state::<AppDatabase>();
+    let done = fetch_wish_counts(&client, &db, job_id, &item_ids, locale, |progress| {
+        let _ = app.emit("wish-count-progress", progress);
+    })
+    .await;
+    let _ = app.emit("wish-count-done", done);
+}
+
+/// The fetch loop behind `run_wish_count_job`.
+async fn fetch_wish_counts(
+    client: &BoothClient,
+    db: &AppDatabase,
+    job_id: u64,
+    item_ids: &[i64],
+    locale: Locale,
+    mut on_progress: impl FnMut(WishCountProgress),
+) -> WishCountDone {
+    let total = item_ids.len();
+    let mut completed = 0;
+    let mut error = None;
+
+    for &item_id in item_ids {
+        if CURRENT_JOB.load(Ordering::Acquire) != job_id {
+            log::info!(
+                "Wish count job {} replaced after {} items",
+                job_id,
+                completed
+            );
+            error = Some(AppError::Cancelled);
+            break;
+        }
+        let wish_count = match client
+            .get_item_json(item_id, locale, Priority::Enrich)
+            .await
+        {
+            Ok(detail) => {
+                let item = detail.into_item(locale);
+                let count = item.wish_lists_count.unwrap_or(0);
+                let item = BoothItem {
+                    wish_lists_count: Some(count),
+                    ..item
+                };
+                let saved = db
+                    .conn_mut()
+                    .and_then(|mut conn| write_cached_items(&mut conn, &[item], true));
+                if let Err(e) = saved {
+                    log::warn!("Failed to cache wish count for item {}: {}", item_id, e);
+                }
+                Some(count)
+            }
+            Err(e) => {
+                log::warn!("Failed to fetch wish count for item {}: {}", item_id, e);
+                None
+            }
+        };
+
+        completed += 1;
+        on_progress(WishCountProgress {
+            job_id,
+            item_id,
+            wish_count,
+            completed,
+            total,
+        });
+    }
+
+    WishCountDone {
+        job_id,
+        completed,
+        total,
+        error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booth::mock_server::{MockBooth, MockResponse, ITEM_JSON};
+
+    #[test]
+    fn only_missing_or_stale_counts_are_fetched_and_newer_jobs_take_over() {
+        let server = MockBooth::start();
+        for id in [1, 2, 3] {
+            let json = ITEM_JSON.replace("5123456", &id.to_string());
+            server.on(
+                &format!("booth.test/ja/items/{}.json", id),
+                MockResponse::json(&json),
+            );
+        }
+        let client = server.client(&[]);
+        let db = AppDatabase::open_in_memory().unwrap();
+        db.conn()
+            .unwrap()
+            .execute_batch(
+                "INSERT INTO cached_items (id, name, price, url, wish_count, wish_count_at)
+                 VALUES (1, 'fresh', 0, '', 10, datetime('now')),
+                        (2, 'stale', 0, '', 20, datetime('now', '-2 days'));",
+            )
+            .unwrap();
+
+        let (cached, pending) = split_fresh(&db, vec![3, 1, 2, 3]).unwrap();
+        assert_eq!(cached, HashMap::from([(1, 10)]));
+        assert_eq!(pending, vec![2, 3]);
+
+        // One progress event per fetched item, and the counts are cached
+        CURRENT_JOB.store(100, Ordering::Release);
+        let mut events = Vec::new();
+        let done = tauri::async_runtime::block_on(fetch_wish_counts(
+            &client,
+            &db,
+            100,
+            &pending,
+            Locale::Ja,
+            |p| events.push((p.item_id, p.wish_count, p.completed)),
+        ));
+        assert_eq!(events, vec![(2, Some(842), 1), (3, Some(842), 2)]);
+        assert!(done.error.is_none());
+        assert_eq!(
+            server.requests(),
+            vec!["booth.test/ja/items/2.json", "booth.test/ja/items/3.json"]
+        );
+        let (cached, pending) = split_fresh(&db, vec![1, 2, 3]).unwrap();
+        assert_eq!(cached.len(), 3);
+        assert!(pending.is_empty());
+
+        // A newer job stops this one before its next fetch
+        let done = tauri::async_runtime::block_on(fetch_wish_counts(
+            &client,
+            &db,
+            100,
+            &[1, 2, 3],
+            Locale::Ja,
+            |_| CURRENT_JOB.store(101, Ordering::Release),
+        ));
+        assert_eq!(done.completed, 1);
+        assert!(matches!(done.error, Some(AppError::Cancelled)));
+        assert_eq!(server.requests().len(), 3);
+    }
+}
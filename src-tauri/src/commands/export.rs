@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::booth::models::{item_url, Locale};
+use crate::database::AppDatabase;
+use crate::error::AppResult;
+
+use super::db::{read_favorites, FavoritesFilter};
+
+// ── Types ──────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl ExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+        }
+    }
+
+    fn mime_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Json => "application/json",
+            ExportFormat::Markdown => "text/markdown",
+        }
+    }
+}
+
+/// One exported favorite; also the object shape of the JSON export.
+#[derive(Debug, Serialize)]
+pub struct ExportRow {
+    pub item_id: i64,
+    pub name: String,
+    pub url: String,
+    pub price: i64,
+    pub shop_name: Option<String>,
+    pub category_name: Option<String>,
+    pub tags: Vec<String>,
+    /// Collection names
+    pub collections: Vec<String>,
+    pub note: Option<String>,
+    pub added_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FavoritesExport {
+    /// Suggested file name, e.g. `boothhunter-favorites-20260101-120000.csv`
+    pub file_name: String,
+    pub mime_type: &'static str,
+    pub content: String,
+    /// Exported favorites
+    pub count: usize,
+}
+
+// ── Commands ───────────────────────────────────────────
+
+/// Render the favorites matching `filter` (the favorites list filters,
+/// newest first) as CSV, JSON or Markdown. The frontend saves or copies
+/// `content`; nothing is written to disk here.
+#[tauri::command]
+pub fn export_favorites(
+    db: State<'_, AppDatabase>,
+    format: ExportFormat,
+    filter: Option<FavoritesFilter>,
+) -> AppResult<FavoritesExport> {
+    build_export(&*db.conn()?, format, &filter.unwrap_or_default())
+}
+
+pub(crate) fn build_export(
+    conn: &Connection,
+    format: ExportFormat,
+    filter: &FavoritesFilter,
+) -> AppResult<FavoritesExport> {
+    let rows = export_rows(conn, filter)?;
+    let content = match format {
+        ExportFormat::Csv => to_csv(&rows),
+        ExportFormat::Json => {
+            serde_json::to_string_pretty(&rows).expect("export rows always serialize")
+        }
+        ExportFormat::Markdown => to_markdown(&rows),
+    };
+    let stamp: String = conn.query_row(
+        "SELECT strftime('%Y%m%d-%H%M%S', 'now', 'localtime')",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(FavoritesExport {
+        file_name: format!("boothhunter-favorites-{}.{}", stamp, format.extension()),
+        mime_type: format.mime_type(),
+        content,
+        count: rows.len(),
+    })
+}
+
+fn export_rows(conn: &Connection, filter: &FavoritesFilter) -> AppResult<Vec<ExportRow>> {
+    let favorites = read_favorites(conn, filter)?;
+
+    // Whole-library lookups: an export is usually most of the favorites, and
+    // an `IN` list of every id could exceed SQLite's variable limit
+    let mut tags: HashMap<i64, Vec<String>> = HashMap::new();
+    let mut stmt = conn.prepare("SELECT item_id, tag FROM item_tags ORDER BY tag")?;
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        tags.entry(row.get(0)?).or_default().push(row.get(1)?);
+    }
+    let mut collections: HashMap<i64, Vec<String>> = HashMap::new();
+    let mut stmt = conn.prepare(
+        "SELECT ci.item_id, c.name FROM collection_items ci
+         JOIN collections c ON c.id = ci.collection_id
+         ORDER BY c.name",
+    )?;
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        collections
+            .entry(row.get(0)?)
+            .or_default()
+            .push(row.get(1)?);
+    }
+
+    Ok(favorites
+        .into_iter()
+        .map(|f| ExportRow {
+            url: item_url(Locale::default(), f.item_id),
+            tags: tags.remove(&f.item_id).unwrap_or_default(),
+            collections: collections.remove(&f.item_id).unwrap_or_default(),
+            item_id: f.item_id,
+            name: f.name,
+            price: f.price,
+            shop_name: f.shop_name,
+            category_name: f.category_name,
+            note: f.note,
+            added_at: f.added_at,
+        })
+        .collect())
+}
+
+const HEADERS: [&str; 9] = [
+    "name",
+    "url",
+    "price",
+    "shop",
+    "category",
+    "tags",
+    "collections",
+    "note",
+    "added_at",
+];
+
+fn columns(row: &ExportRow) -> [String; 9] {
+    [
+        row.name.clone(),
+        row.url.clone(),
+        row.price.to_string(),
+        row.shop_name.clone().unwrap_or_default(),
+        row.category_name.clone().unwrap_or_default(),
+        row.tags.join(", "),
+        row.collections.join(", "),
+        row.note.clone().unwrap_or_default(),
+        row.added_at.clone(),
+    ]
+}
+
+fn to_csv(rows: &[ExportRow]) -> String {
+    // BOM so Excel reads the Japanese text as UTF-8
+    let mut out = String::from('\u{feff}');
+    out.push_str(&HEADERS.join(","));
+    out.push_str("\r\n");
+    for row in rows {
+        let fields: Vec<String> = columns(row).iter().map(|f| csv_field(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    // Spreadsheets run cells starting with these as formulas
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn to_markdown(rows: &[ExportRow]) -> String {
+    let mut out = String::from(
+        "| Name | Price | Shop | Category | Tags | Collections | Note | Added |\n\
+         | --- | ---: | --- | --- | --- | --- | --- | --- |\n",
+    );
+    for row in rows {
+        let [name, url, price, shop, category, tags, collections, note, added_at] = columns(row);
+        let cells = [
+            format!(
+                "[{}]({})",
+                markdown_cell(&name).replace(['[', ']'], ""),
+                url
+            ),
+            format!("¥{}", price),
+            markdown_cell(&shop),
+            markdown_cell(&category),
+            markdown_cell(&tags),
+            markdown_cell(&collections),
+            markdown_cell(&note),
+            // Date only; the time of day is noise in a shared list
+            added_at.chars().take(10).collect(),
+        ];
+        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    out
+}
+
+/// Keep a value inside one table cell.
+fn markdown_cell(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace(['\r', '\n'], "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library() -> AppDatabase {
+        let db = AppDatabase::open_in_memory().unwrap();
+        db.conn().unwrap().execute_batch(
+            "INSERT INTO favorites (item_id, name, price, shop_name, category_name, note, added_at)
+             VALUES (1, 'ふわもこパーカー', 1500, 'ぽんぽこ工房', '3D衣装', '色違い, \"黒\"も
+欲しい', '2026-01-02 10:00:00'),
+                    (2, '=HYPERLINK(1)|x', 800, NULL, NULL, NULL, '2026-01-01 09:00:00');
+             INSERT INTO item_tags (item_id, tag) VALUES (1, 'winter'), (1, 'kipfel');
+             INSERT INTO collections (id, name) VALUES (1, 'Shopping list');
+             INSERT INTO collection_items (collection_id, item_id) VALUES (1, 1);",
+        )
+        .unwrap();
+        db
+    }
+
+    #[test]
+    fn exports_every_format() {
+        let db = library();
+        let conn = db.conn().unwrap();
+
+        let csv = build_export(&conn, ExportFormat::Csv, &FavoritesFilter::default()).unwrap();
+        assert_eq!(csv.count, 2);
+        assert!(csv.file_name.ends_with(".csv"));
+        let lines: Vec<&str> = csv
+            .content
+            .trim_start_matches('\u{feff}')
+            .split("\r\n")
+            .collect();
+        assert_eq!(
+            lines[0],
+            "name,url,price,shop,category,tags,collections,note,added_at"
+        );
+        assert_eq!(
+            lines[1],
+            "ふわもこパーカー,https://booth.pm/ja/items/1,1500,ぽんぽこ工房,3D衣装,\
+             \"kipfel, winter\",Shopping list,\"色違い, \"\"黒\"\"も\n欲しい\",2026-01-02 10:00:00"
+        );
+        assert!(lines[2].starts_with("'=HYPERLINK(1)|x,"));
+
+        let json = build_export(&conn, ExportFormat::Json, &FavoritesFilter::default()).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json.content).unwrap();
+        assert_eq!(parsed[0]["tags"], serde_json::json!(["kipfel", "winter"]));
+        assert_eq!(
+            parsed[0]["collections"],
+            serde_json::json!(["Shopping list"])
+        );
+        assert_eq!(parsed[1]["shop_name"], serde_json::Value::Null);
+
+        let md = build_export(&conn, ExportFormat::Markdown, &FavoritesFilter::default()).unwrap();
+        let lines: Vec<&str> = md.content.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[2],
+            "| [ふわもこパーカー](https://booth.pm/ja/items/1) | ¥1500 | ぽんぽこ工房 | 3D衣装 \
+             | kipfel, winter | Shopping list | 色違い, \"黒\"も<br>欲しい | 2026-01-02 |"
+        );
+        assert!(lines[3].starts_with("| [=HYPERLINK(1)\\|x](https://booth.pm/ja/items/2) |"));
+    }
+
+    #[test]
+    fn export_applies_the_favorites_filter() {
+        let db = library();
+        let conn = db.conn().unwrap();
+        let filter = FavoritesFilter {
+            tags: vec!["winter".to_string()],
+            ..Default::default()
+        };
+        let export = build_export(&conn, ExportFormat::Json, &filter).unwrap();
+        assert_eq!(export.count, 1);
+        assert!(export.content.contains("ふわもこパーカー"));
+
+        let filter = FavoritesFilter {
+            price_max: Some(100),
+            ..Default::default()
+        };
+        let export = build_export(&conn, ExportFormat::Csv, &filter).unwrap();
+        assert_eq!(export.count, 0);
+        assert_eq!(export.content.lines().count(), 1);
+    }
+}
@@ -0,0 +1,497 @@
+use std::collections::HashSet;
+
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::database::AppDatabase;
+use crate::error::{AppError, AppResult};
+
+use super::db::escape_like;
+
+/// Trigram tokens are three characters long; shorter terms can't use the index
+const MIN_INDEXED_TERM_CHARS: usize = 3;
+const DEFAULT_LIMIT: u32 = 50;
+const MAX_LIMIT: u32 = 500;
+
+/// Wrap matches in `snippet`; control characters can't clash with item text.
+pub const HIGHLIGHT_START: char = '\u{2}';
+pub const HIGHLIGHT_END: char = '\u{3}';
+
+// ── Types ──────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FullTextScope {
+    /// Favorites first-hand (name, shop, note, tags) and every cached item
+    #[default]
+    All,
+    Favorites,
+    Cache,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FullTextSource {
+    Favorites,
+    Cache,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FullTextHit {
+    pub item_id: i64,
+    pub name: String,
+    /// Best-matching column excerpt; matches sit between `HIGHLIGHT_START`
+    /// and `HIGHLIGHT_END`
+    pub snippet: String,
+    pub source: FullTextSource,
+    pub favorite: bool,
+    /// bm25 score, or the recency position for short-term scans; lower is better
+    pub rank: f64,
+}
+
+// ── Commands ───────────────────────────────────────────
+
+/// Ranked search over the trigram indexes of favorites and cached items.
+///
+/// Every whitespace-separated term must match. Terms of 3+ characters go
+/// through the index and are highlighted; shorter ones only narrow the hits.
+/// A query of short terms only (`衣装`, `의상`) scans the indexed columns
+/// instead, newest first.
+#[tauri::command]
+pub fn full_text_search(
+    db: State<'_, AppDatabase>,
+    query: String,
+    scope: Option<FullTextScope>,
+    limit: Option<u32>,
+) -> AppResult<Vec<FullTextHit>> {
+    search(
+        &*db.conn()?,
+        &query,
+        scope.unwrap_or_default(),
+        limit.unwrap_or(DEFAULT_LIMIT),
+    )
+}
+
+pub(crate) fn search(
+    conn: &Connection,
+    query: &str,
+    scope: FullTextScope,
+    limit: u32,
+) -> AppResult<Vec<FullTextHit>> {
+    let limit = limit.clamp(1, MAX_LIMIT);
+    let (long, short): (Vec<&str>, Vec<&str>) = query
+        .split_whitespace()
+        .partition(|t| t.chars().count() >= MIN_INDEXED_TERM_CHARS);
+    if long.is_empty() && short.is_empty() {
+        return Err(AppError::Validation("Search query is empty".to_string()));
+    }
+    // Quoted, so FTS5 operators and punctuation in the query are plain text
+    let match_expr = (!long.is_empty()).then(|| {
+        long.iter()
+            .map(|t| format!("\"{}\"", t.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" ")
+    });
+
+    let mut hits = Vec::new();
+    for (index, skip) in [
+        (&FAVORITES_INDEX, FullTextScope::Cache),
+        (&CACHE_INDEX, FullTextScope::Favorites),
+    ] {
+        if scope != skip {
+            hits.extend(search_table(
+                conn,
+                index,
+                match_expr.as_deref(),
+                &short,
+                limit,
+            )?);
+        }
+    }
+
+    // A favorite is usually cached too; keep its best-ranked hit
+    hits.sort_by(|a, b| a.rank.total_cmp(&b.rank));
+    let mut seen = HashSet::new();
+    hits.retain(|hit| seen.insert(hit.item_id));
+    hits.truncate(limit as usize);
+    Ok(hits)
+}
+
+/// One FTS5 table and how to read hits out of it.
+struct FtsIndex {
+    table: &'static str,
+    /// Indexed columns, for the LIKE filters of short terms
+    columns: &'static [&'static str],
+    /// bm25 column weights: names count most
+    weights: &'static str,
+    /// Joins the content row as `c`, exposing `c.name`
+    join: &'static str,
+    /// Whether the item is a favorite, in terms of `c`
+    favorite: &'static str,
+    /// Newest first, in terms of `c`; orders scans without a MATCH
+    recency: &'static str,
+    source: FullTextSource,
+}
+
+const FAVORITES_INDEX: FtsIndex = FtsIndex {
+    table: "favorites_fts",
+    columns: &["name", "shop_name", "note", "tags"],
+    weights: "10.0, 2.0, 5.0, 5.0",
+    join: "JOIN favorites c ON c.item_id = favorites_fts.rowid",
+    favorite: "1",
+    recency: "c.added_at DESC",
+    source: FullTextSource::Favorites,
+};
+
+const CACHE_INDEX: FtsIndex = FtsIndex {
+    table: "cached_items_fts",
+    columns: &["name", "description", "shop_name", "tags_json"],
+    weights: "10.0, 1.0, 2.0, 5.0",
+    join: "JOIN cached_items c ON c.id = cached_items_fts.rowid",
+    favorite: "c.id IN (SELECT item_id FROM favorites)",
+    recency: "c.cached_at DESC",
+    source: FullTextSource::Cache,
+};
+
+/// Hits from one index: ranked through MATCH when there is a `match_expr`,
+/// otherwise a LIKE scan in recency order.
+fn search_table(
+    conn: &Connection,
+    index: &FtsIndex,
+    match_expr: Option<&str>,
+    short_terms: &[&str],
+    limit: u32,
+) -> AppResult<Vec<FullTextHit>> {
+    let mut conditions = Vec::new();
+    let mut values = Vec::new();
+    if let Some(match_expr) = match_expr {
+        conditions.push(format!("{} MATCH ?", index.table));
+        values.push(Value::Text(match_expr.to_string()));
+    }
+    for term in short_terms {
+        let any_column = index
+            .columns
+            .iter()
+            .map(|col| format!("{}.{} LIKE ? ESCAPE '\\'", index.table, col))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        conditions.push(format!("({})", any_column));
+        let pattern = format!("%{}%", escape_like(term));
+        values.extend(index.columns.iter().map(|_| Value::Text(pattern.clone())));
+    }
+    let columns = index
+        .columns
+        .iter()
+        .map(|col| format!("{}.{}", index.table, col))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if match_expr.is_none() {
+        let sql = format!(
+            "SELECT {table}.rowid, c.name, {favorite}, {columns}
+             FROM {table} {join}
+             WHERE {conditions}
+             ORDER BY {recency} LIMIT {limit}",
+            table = index.table,
+            favorite = index.favorite,
+            columns = columns,
+            join = index.join,
+            conditions = conditions.join(" AND "),
+            recency = index.recency,
+            limit = limit,
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let mut hits = stmt
+            .query_map(params_from_iter(values.iter()), |row| {
+                let mut texts = Vec::new();
+                for i in 0..index.columns.len() {
+                    texts.push(row.get::<_, Option<String>>(3 + i)?);
+                }
+                Ok(FullTextHit {
+                    item_id: row.get(0)?,
+                    name: row.get(1)?,
+                    snippet: like_snippet(&texts, short_terms),
+                    favorite: row.get(2)?,
+                    rank: 0.0,
+                    source: index.source,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        // No bm25 without MATCH: rank by recency so the merge keeps the order
+        for (i, hit) in hits.iter_mut().enumerate() {
+            hit.rank = i as f64;
+        }
+        return Ok(hits);
+    }
+
+    // Snippet markers come first in placeholder order
+    values.splice(
+        0..0,
+        [
+            Value::Text(HIGHLIGHT_START.to_string()),
+            Value::Text(HIGHLIGHT_END.to_string()),
+        ],
+    );
+    let sql = format!(
+        "SELECT {table}.rowid, c.name,
+                snippet({table}, -1, ?, ?, '…', 16),
+                {favorite}, bm25({table}, {weights}) AS score
+         FROM {table} {join}
+         WHERE {conditions}
+         ORDER BY score LIMIT {limit}",
+        table = index.table,
+        favorite = index.favorite,
+        weights = index.weights,
+        join = index.join,
+        conditions = conditions.join(" AND "),
+        limit = limit,
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map(params_from_iter(values.iter()), |row| {
+            Ok(FullTextHit {
+                item_id: row.get(0)?,
+                name: row.get(1)?,
+                snippet: row.get(2)?,
+                favorite: row.get(3)?,
+                rank: row.get(4)?,
+                source: index.source,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Characters of context kept around the first match, like `snippet`'s window
+const SNIPPET_CONTEXT_CHARS: usize = 16;
+
+/// `snippet` stand-in for LIKE scans: an excerpt of the first column that
+/// contains a term, with every term highlighted. Case folding is ASCII-only,
+/// as with SQLite's LIKE.
+fn like_snippet(texts: &[Option<String>], terms: &[&str]) -> String {
+    let terms: Vec<String> = terms.iter().map(|t| t.to_ascii_lowercase()).collect();
+    let first_match = |lower: &str| terms.iter().filter_map(|t| lower.find(t.as_str())).min();
+    let Some((text, pos)) = texts
+        .iter()
+        .flatten()
+        .find_map(|text| first_match(&text.to_ascii_lowercase()).map(|pos| (text.as_str(), pos)))
+    else {
+        return texts.first().cloned().flatten().unwrap_or_default();
+    };
+
+    // Byte offsets of the excerpt, on char boundaries
+    let start = text[..pos]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_CONTEXT_CHARS - 1)
+        .map_or(0, |(i, _)| i);
+    let end = text[pos..]
+        .char_indices()
+        .nth(SNIPPET_CONTEXT_CHARS * 2)
+        .map_or(text.len(), |(i, _)| pos + i);
+    let excerpt = &text[start..end];
+    let lower = excerpt.to_ascii_lowercase();
+
+    let mut out = String::new();
+    if start > 0 {
+        out.push('…');
+    }
+    let mut i = 0;
+    while i < excerpt.len() {
+        let hit = terms
+            .iter()
+            .filter(|t| !t.is_empty() && lower[i..].starts_with(t.as_str()))
+            .map(|t| t.len())
+            .max();
+        match hit {
+            Some(len) => {
+                out.push(HIGHLIGHT_START);
+                out.push_str(&excerpt[i..i + len]);
+                out.push(HIGHLIGHT_END);
+                i += len;
+            }
+            None => {
+                let c = excerpt[i..].chars().next().expect("i is a char boundary");
+                out.push(c);
+                i += c.len_utf8();
+            }
+        }
+    }
+    if end < text.len() {
+        out.push('…');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booth::models::BoothItem;
+    use crate::commands::db::write_cached_items;
+    use rusqlite::params;
+
+    fn cached(id: i64, name: &str, description: &str) -> BoothItem {
+        BoothItem {
+            id,
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            price: 1000,
+            category_name: None,
+            shop_name: Some("ぽんぽこ工房".to_string()),
+            url: format!("https://booth.pm/ja/items/{}", id),
+            images: vec![],
+            tags: vec![],
+            wish_lists_count: None,
+            variations: vec![],
+            locale: Default::default(),
+        }
+    }
+
+    fn ids(conn: &Connection, query: &str, scope: FullTextScope) -> Vec<i64> {
+        search(conn, query, scope, 50)
+            .unwrap()
+            .iter()
+            .map(|h| h.item_id)
+            .collect()
+    }
+
+    #[test]
+    fn indexes_follow_favorites_tags_and_cache() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        let mut conn = db.conn_mut().unwrap();
+        write_cached_items(
+            &mut conn,
+            &[
+                cached(
+                    1,
+                    "【Kipfel対応】ふわもこパーカー",
+                    "キプフェル専用の衣装です",
+                ),
+                cached(2, "ブーツ", "マヌカちゃん対応"),
+            ],
+            true,
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO favorites (item_id, name, price) VALUES (1, '【Kipfel対応】ふわもこパーカー', 1000)",
+            [],
+        )
+        .unwrap();
+
+        // Partial matches inside Japanese text
+        assert_eq!(ids(&conn, "もこパー", FullTextScope::All), vec![1]);
+        assert_eq!(ids(&conn, "マヌカ", FullTextScope::Cache), vec![2]);
+        assert!(ids(&conn, "マヌカ", FullTextScope::Favorites).is_empty());
+
+        // Tags and notes of favorites
+        conn.execute(
+            "INSERT INTO item_tags (item_id, tag) VALUES (1, 'winter-outfit')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE favorites SET note = '色違いも欲しい' WHERE item_id = 1",
+            [],
+        )
+        .unwrap();
+        assert_eq!(ids(&conn, "WINTER", FullTextScope::Favorites), vec![1]);
+        assert_eq!(ids(&conn, "色違い", FullTextScope::Favorites), vec![1]);
+        conn.execute("DELETE FROM item_tags WHERE item_id = 1", [])
+            .unwrap();
+        assert!(ids(&conn, "winter", FullTextScope::Favorites).is_empty());
+
+        // Cache rewrites and eviction
+        write_cached_items(&mut conn, &[cached(2, "ロングブーツ", "")], true).unwrap();
+        assert_eq!(ids(&conn, "ロングブ", FullTextScope::Cache), vec![2]);
+        conn.execute("DELETE FROM cached_items WHERE id = ?1", params![2])
+            .unwrap();
+        assert!(ids(&conn, "ロングブ", FullTextScope::Cache).is_empty());
+
+        conn.execute("DELETE FROM favorites WHERE item_id = 1", [])
+            .unwrap();
+        assert!(ids(&conn, "もこパー", FullTextScope::Favorites).is_empty());
+    }
+
+    #[test]
+    fn hits_are_ranked_highlighted_and_deduplicated() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        let mut conn = db.conn_mut().unwrap();
+        write_cached_items(
+            &mut conn,
+            &[
+                cached(1, "髪型セット", "ポニーテールとツインテールの詰め合わせ"),
+                cached(2, "ポニーテール", "髪型"),
+            ],
+            true,
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO favorites (item_id, name, price) VALUES (2, 'ポニーテール', 1000)",
+            [],
+        )
+        .unwrap();
+
+        let hits = search(&conn, "ポニーテール", FullTextScope::All, 50).unwrap();
+        // A name match outranks a description match, and item 2 appears once
+        assert_eq!(
+            hits.iter().map(|h| h.item_id).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert!(hits[0].favorite);
+        assert!(!hits[1].favorite);
+        assert!(hits[1]
+            .snippet
+            .contains(&format!("{}ポニーテール{}", HIGHLIGHT_START, HIGHLIGHT_END)));
+
+        // Short terms narrow without the index
+        assert_eq!(ids(&conn, "ポニーテール 詰め", FullTextScope::All), vec![1]);
+        // Quotes and FTS5 syntax are taken literally
+        assert!(ids(&conn, "\"NEAR( OR", FullTextScope::All).is_empty());
+        assert!(matches!(
+            search(&conn, "  ", FullTextScope::All, 50),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn short_queries_scan_newest_first() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        let mut conn = db.conn_mut().unwrap();
+        write_cached_items(
+            &mut conn,
+            &[
+                cached(1, "衣装セット", "夏用"),
+                cached(2, "ブーツ", "冬の衣装に合わせて"),
+                cached(3, "髪飾り", "100%手作り"),
+            ],
+            true,
+        )
+        .unwrap();
+        conn.execute_batch(
+            "UPDATE cached_items SET cached_at = '2026-01-01 00:00:00' WHERE id = 1;
+             UPDATE cached_items SET cached_at = '2026-01-02 00:00:00' WHERE id = 2;
+             INSERT INTO favorites (item_id, name, price, note, added_at)
+             VALUES (4, '의상 세트', 1000, NULL, '2026-01-03 00:00:00');",
+        )
+        .unwrap();
+
+        // Two-character CJK words have no trigram, but still find items
+        assert_eq!(ids(&conn, "衣装", FullTextScope::All), vec![2, 1]);
+        assert_eq!(ids(&conn, "의상", FullTextScope::Favorites), vec![4]);
+        let hits = search(&conn, "衣装", FullTextScope::Cache, 50).unwrap();
+        assert_eq!(
+            hits[0].snippet,
+            format!("冬の{}衣装{}に合わせて", HIGHLIGHT_START, HIGHLIGHT_END)
+        );
+
+        // LIKE wildcards in terms are literal
+        assert_eq!(ids(&conn, "%", FullTextScope::All), vec![3]);
+        assert!(ids(&conn, "_", FullTextScope::All).is_empty());
+        assert_eq!(
+            ids(&conn, "ブーツ %", FullTextScope::All),
+            Vec::<i64>::new()
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use tauri::State;
+
+use crate::database::AppDatabase;
+use crate::error::AppResult;
+use crate::image_cache::{ImageCache, ImageCacheUsage};
+
+#[tauri::command]
+pub fn get_image_cache_usage(
+    cache: State<'_, ImageCache>,
+    db: State<'_, AppDatabase>,
+) -> AppResult<ImageCacheUsage> {
+    cache.usage(&db)
+}
+
+/// Delete every cached image; they are downloaded again on next view.
+#[tauri::command]
+pub fn clear_image_cache(
+    cache: State<'_, ImageCache>,
+    db: State<'_, AppDatabase>,
+) -> AppResult<()> {
+    cache.clear(&db)
+}
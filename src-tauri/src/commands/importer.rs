@@ -0,0 +1,356 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use regex::Regex;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::booth::client::BoothClient;
+use crate::booth::models::{card_thumbnail, BoothItem, Locale};
+use crate::booth::scheduler::Priority;
+use crate::database::AppDatabase;
+use crate::error::{AppError, AppResult};
+
+use super::booth::fetch_and_cache_item;
+use super::collections::{ensure_collection, normalize_tag};
+use super::db::{insert_favorite, AddFavoriteParams};
+
+/// Upper bound for one import; at the background request rate this is
+/// already hours of fetching.
+const MAX_IMPORT_ITEMS: usize = 5000;
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+
+// ── Types ──────────────────────────────────────────────
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ImportOptions {
+    /// User tags added to every imported item
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Collection every imported item is filed into
+    pub collection_id: Option<i64>,
+    /// Locale to fetch item details in
+    pub locale: Option<Locale>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportJob {
+    pub job_id: u64,
+    /// Distinct item ids found in the input
+    pub found: usize,
+    /// Of those, items that were already favorites; they are not fetched again
+    pub duplicates: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    Added,
+    /// Already a favorite; tags and collection still apply
+    Duplicate,
+    Failed,
+}
+
+/// Emitted as `import-items-progress` after each item.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportProgress {
+    pub job_id: u64,
+    pub item_id: i64,
+    pub status: ImportStatus,
+    pub completed: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportFailure {
+    pub item_id: i64,
+    pub error: AppError,
+}
+
+/// Emitted as `import-items-done` once the job ends.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportSummary {
+    pub job_id: u64,
+    pub found: usize,
+    pub added: Vec<i64>,
+    pub duplicates: Vec<i64>,
+    pub failed: Vec<ImportFailure>,
+}
+
+// ── Commands ───────────────────────────────────────────
+
+/// Import Booth items as favorites from pasted text or a Netscape bookmarks
+/// HTML export. Every item URL form is recognised (`booth.pm/{lang}/items/ID`,
+/// `{shop}.booth.pm/items/ID`, `.json` endpoints).
+///
+/// New items are fetched at `Background` priority and added as favorites;
+/// items that already are favorites are reported as duplicates. `options`
+/// tags or files all of them. Progress is streamed as `import-items-progress`
+/// and the report arrives as `import-items-done`.
+#[tauri::command]
+pub fn import_items(
+    app: AppHandle,
+    source: String,
+    options: Option<ImportOptions>,
+) -> AppResult<ImportJob> {
+    let options = options.unwrap_or_default();
+    let item_ids = extract_item_ids(&source);
+    if item_ids.is_empty() {
+        return Err(AppError::Validation("No Booth item URLs found".to_string()));
+    }
+    if item_ids.len() > MAX_IMPORT_ITEMS {
+        return Err(AppError::Validation(format!(
+            "Too many items (max {})",
+            MAX_IMPORT_ITEMS
+        )));
+    }
+    let duplicates = {
+        let db = app.state::<AppDatabase>();
+        let conn = db.conn()?;
+        if let Some(collection_id) = options.collection_id {
+            ensure_collection(&conn, collection_id)?;
+        }
+        existing_favorites(&conn, &item_ids)?
+    };
+
+    let job_id = NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed);
+    let job = ImportJob {
+        job_id,
+        found: item_ids.len(),
+        duplicates: item_ids
+            .iter()
+            .copied()
+            .filter(|id| duplicates.contains(id))
+            .collect(),
+    };
+    tauri::async_runtime::spawn(async move {
+        let client = app.state::<BoothClient>();
+        let db = app.state::<AppDatabase>();
+        let summary = run_import(
+            &client,
+            &db,
+            job_id,
+            &item_ids,
+            &duplicates,
+            &options,
+            |progress| {
+                let _ = app.emit("import-items-progress", progress);
+            },
+        )
+        .await;
+        let _ = app.emit("import-items-done", summary);
+    });
+    Ok(job)
+}
+
+/// Booth item ids in `text`, in order of first appearance.
+pub(crate) fn extract_item_ids(text: &str) -> Vec<i64> {
+    // The shop subdomain form matches from `booth.pm` on; the language
+    // segment is optional, and `.json` or a query string may follow the id
+    let re = Regex::new(r"(?i)booth\.pm/(?:[a-z]{2}(?:-[a-z]+)?/)?items/(\d+)")
+        .expect("static regex must be valid");
+    let mut seen = HashSet::new();
+    re.captures_iter(text)
+        .filter_map(|c| c[1].parse::<i64>().ok())
+        .filter(|id| seen.insert(*id))
+        .collect()
+}
+
+fn existing_favorites(conn: &Connection, item_ids: &[i64]) -> AppResult<HashSet<i64>> {
+    let mut stmt = conn.prepare("SELECT 1 FROM favorites WHERE item_id = ?1")?;
+    let mut existing = HashSet::new();
+    for id in item_ids {
+        if stmt.exists(params![id])? {
+            existing.insert(*id);
+        }
+    }
+    Ok(existing)
+}
+
+async fn run_import(
+    client: &BoothClient,
+    db: &AppDatabase,
+    job_id: u64,
+    item_ids: &[i64],
+    duplicates: &HashSet<i64>,
+    options: &ImportOptions,
+    mut on_progress: impl FnMut(ImportProgress),
+) -> ImportSummary {
+    let mut summary = ImportSummary {
+        job_id,
+        found: item_ids.len(),
+        ..Default::default()
+    };
+    let locale = options.locale.unwrap_or_default();
+
+    for (i, &item_id) in item_ids.iter().enumerate() {
+        let result = if duplicates.contains(&item_id) {
+            db.conn()
+                .and_then(|conn| file_item(&conn, item_id, options))
+                .map(|()| ImportStatus::Duplicate)
+        } else {
+            fetch_and_cache_item(client, db, item_id, locale, Priority::Background)
+                .await
+                .and_then(|item| match add_item(&*db.conn()?, &item, options)? {
+                    true => Ok(ImportStatus::Added),
+                    // Favorited from elsewhere while the import was running
+                    false => Ok(ImportStatus::Duplicate),
+                })
+        };
+
+        let status = match result {
+            Ok(status) => status,
+            Err(e) => {
+                log::warn!("Import job {}: item {} failed: {}", job_id, item_id, e);
+                summary.failed.push(ImportFailure { item_id, error: e });
+                ImportStatus::Failed
+            }
+        };
+        match status {
+            ImportStatus::Added => summary.added.push(item_id),
+            ImportStatus::Duplicate => summary.duplicates.push(item_id),
+            ImportStatus::Failed => {}
+        }
+        on_progress(ImportProgress {
+            job_id,
+            item_id,
+            status,
+            completed: i + 1,
+            total: item_ids.len(),
+        });
+    }
+    summary
+}
+
+/// Add a fetched item as a favorite and file it; returns whether it was new.
+fn add_item(conn: &Connection, item: &BoothItem, options: &ImportOptions) -> AppResult<bool> {
+    let added = insert_favorite(
+        conn,
+        &AddFavoriteParams {
+            item_id: item.id,
+            name: item.name.clone(),
+            price: item.price,
+            // Same size as favorites added from search results
+            thumbnail_url: item.images.first().map(|url| card_thumbnail(url)),
+            category_name: item.category_name.clone(),
+            shop_name: item.shop_name.clone(),
+        },
+    )?;
+    file_item(conn, item.id, options)?;
+    Ok(added)
+}
+
+/// Apply the import's tags and collection to one item.
+fn file_item(conn: &Connection, item_id: i64, options: &ImportOptions) -> AppResult<()> {
+    for tag in options.tags.iter().filter_map(|t| normalize_tag(t)) {
+        conn.execute(
+            "INSERT OR IGNORE INTO item_tags (item_id, tag) VALUES (?1, ?2)",
+            params![item_id, tag],
+        )?;
+    }
+    if let Some(collection_id) = options.collection_id {
+        conn.execute(
+            "INSERT OR IGNORE INTO collection_items (collection_id, item_id) VALUES (?1, ?2)",
+            params![collection_id, item_id],
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booth::mock_server::{MockBooth, MockResponse, ITEM_JSON};
+
+    #[test]
+    fn item_ids_come_from_every_url_form() {
+        let text = "\
+            https://booth.pm/ja/items/1001 and https://booth.pm/zh-cn/items/1002?foo=1\n\
+            <DT><A HREF=\"https://kipfel.booth.pm/items/1003\" ADD_DATE=\"1\">Kipfel</A>\n\
+            https://booth.pm/en/items/1004.json, booth.pm/items/1005, https://BOOTH.PM/ko/items/1001\n\
+            https://booth.pm/ja/browse/1006 https://example.com/items/1007";
+        assert_eq!(extract_item_ids(text), vec![1001, 1002, 1003, 1004, 1005]);
+        assert!(extract_item_ids("no links here").is_empty());
+    }
+
+    #[test]
+    fn import_adds_files_and_reports() {
+        let server = MockBooth::start();
+        server.on(
+            "booth.test/ja/items/5123456.json",
+            MockResponse::json(ITEM_JSON),
+        );
+        let client = server.client(&[]);
+        let db = AppDatabase::open_in_memory().unwrap();
+        db.conn()
+            .unwrap()
+            .execute_batch(
+                "INSERT INTO favorites (item_id, name, price) VALUES (42, 'old', 100);
+                 INSERT INTO collections (id, name) VALUES (3, 'imported');",
+            )
+            .unwrap();
+
+        let item_ids = vec![5123456, 42, 404];
+        let duplicates = existing_favorites(&db.conn().unwrap(), &item_ids).unwrap();
+        let options = ImportOptions {
+            tags: vec!["wishlist".to_string()],
+            collection_id: Some(3),
+            locale: None,
+        };
+        let mut statuses = Vec::new();
+        let summary = tauri::async_runtime::block_on(run_import(
+            &client,
+            &db,
+            1,
+            &item_ids,
+            &duplicates,
+            &options,
+            |p| statuses.push((p.item_id, p.status, p.completed)),
+        ));
+
+        assert_eq!(
+            statuses,
+            vec![
+                (5123456, ImportStatus::Added, 1),
+                (42, ImportStatus::Duplicate, 2),
+                (404, ImportStatus::Failed, 3),
+            ]
+        );
+        assert_eq!(summary.added, vec![5123456]);
+        assert_eq!(summary.duplicates, vec![42]);
+        assert_eq!(summary.failed.len(), 1);
+        assert!(matches!(summary.failed[0].error, AppError::NotFound(_)));
+
+        // The duplicate was not fetched, and both favorites were tagged and filed
+        assert!(!server.requests().iter().any(|r| r.contains("/items/42")));
+        let conn = db.conn().unwrap();
+        let filed: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM collection_items ci
+                 JOIN item_tags it ON it.item_id = ci.item_id AND it.tag = 'wishlist'
+                 WHERE ci.collection_id = 3",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(filed, 2);
+        let name: String = conn
+            .query_row(
+                "SELECT name FROM favorites WHERE item_id = 5123456",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(!name.is_empty());
+        let thumbnail: String = conn
+            .query_row(
+                "SELECT thumbnail_url FROM favorites WHERE item_id = 5123456",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(thumbnail.contains("/c/300x300_a2_g5/"), "{}", thumbnail);
+    }
+}
@@ -1,5 +1,14 @@
+pub mod booth;
+pub mod bulk;
 pub mod collections;
+pub mod crawl;
 pub mod db;
+pub mod enrich;
+pub mod export;
+pub mod full_text;
+pub mod images;
+pub mod importer;
+pub mod refresh;
 pub mod stats;
 pub mod translation;
 pub mod updater;
@@ -0,0 +1,478 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::booth::client::BoothClient;
+use crate::booth::models::{card_thumbnail, category_key, BoothItem, Locale};
+use crate::booth::scheduler::Priority;
+use crate::database::AppDatabase;
+use crate::error::{AppError, AppResult};
+
+use super::booth::fetch_and_cache_item;
+
+/// How often the auto-refresh loop looks for stale favorites
+const AUTO_REFRESH_TICK: Duration = Duration::from_secs(10 * 60);
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+/// Only one refresh at a time; they all share the Booth rate limit anyway.
+static REFRESH_RUNNING: AtomicBool = AtomicBool::new(false);
+/// Auto-refresh interval in hours; 0 turns it off.
+static AUTO_REFRESH_HOURS: AtomicU32 = AtomicU32::new(0);
+
+// ── Types ──────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct RefreshJob {
+    pub job_id: u64,
+    pub total: usize,
+}
+
+/// Emitted as `favorites-refresh-progress` after each item.
+#[derive(Debug, Clone, Serialize)]
+pub struct RefreshProgress {
+    pub job_id: u64,
+    pub item_id: i64,
+    pub completed: usize,
+    pub total: usize,
+}
+
+/// Emitted as `favorites-refresh-done` once the job ends.
+#[derive(Debug, Clone, Serialize)]
+pub struct RefreshSummary {
+    pub job_id: u64,
+    pub total: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub failed: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FavoriteChange {
+    pub item_id: i64,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub changed_at: String,
+}
+
+// ── Commands ───────────────────────────────────────────
+
+/// Re-fetch favorites from Booth at `Background` priority and update their
+/// stored name, price, thumbnail, category and shop. Every changed field is
+/// logged in `favorite_changes`. `item_ids` limits the refresh to those
+/// favorites; by default all of them are refreshed.
+#[tauri::command]
+pub fn refresh_favorites(app: AppHandle, item_ids: Option<Vec<i64>>) -> AppResult<RefreshJob> {
+    let targets = {
+        let db = app.state::<AppDatabase>();
+        let conn = db.conn()?;
+        favorite_targets(&conn, item_ids.as_deref(), None)?
+    };
+    start_refresh(app, targets)
+}
+
+/// Turn the periodic refresh on (favorites not refreshed for `interval_hours`
+/// are re-fetched) or off (`None`/0). Not persisted; the frontend sets it on startup.
+#[tauri::command]
+pub fn set_favorites_auto_refresh(interval_hours: Option<u32>) {
+    AUTO_REFRESH_HOURS.store(interval_hours.unwrap_or(0), Ordering::Relaxed);
+}
+
+#[tauri::command]
+pub fn get_favorite_changes(
+    db: State<'_, AppDatabase>,
+    item_id: i64,
+) -> AppResult<Vec<FavoriteChange>> {
+    read_favorite_changes(&*db.conn()?, item_id)
+}
+
+fn start_refresh(app: AppHandle, targets: Vec<(i64, Locale)>) -> AppResult<RefreshJob> {
+    let Some(slot) = RefreshSlot::acquire() else {
+        return Err(AppError::Busy(
+            "A favorites refresh is already running".to_string(),
+        ));
+    };
+    let job_id = NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed);
+    let total = targets.len();
+    tauri::async_runtime::spawn(run_refresh(app, job_id, targets, slot));
+    Ok(RefreshJob { job_id, total })
+}
+
+/// Holds `REFRESH_RUNNING` for one job. Dropping it frees the flag, also
+/// when the job panics or returns early.
+struct RefreshSlot;
+
+impl RefreshSlot {
+    fn acquire() -> Option<Self> {
+        (!REFRESH_RUNNING.swap(true, Ordering::AcqRel)).then_some(RefreshSlot)
+    }
+}
+
+impl Drop for RefreshSlot {
+    fn drop(&mut self) {
+        REFRESH_RUNNING.store(false, Ordering::Release);
+    }
+}
+
+/// Background loop behind `set_favorites_auto_refresh`; spawned once at startup.
+pub async fn auto_refresh_loop(app: AppHandle) {
+    loop {
+        tokio::time::sleep(AUTO_REFRESH_TICK).await;
+        let hours = AUTO_REFRESH_HOURS.load(Ordering::Relaxed);
+        if hours == 0 || REFRESH_RUNNING.load(Ordering::Acquire) {
+            continue;
+        }
+
+        let targets = {
+            let db = app.state::<AppDatabase>();
+            let conn = db.conn();
+            conn.and_then(|conn| favorite_targets(&conn, None, Some(hours)))
+        };
+        match targets {
+            Ok(targets) if targets.is_empty() => {}
+            Ok(targets) => {
+                if let Err(e) = start_refresh(app.clone(), targets) {
+                    log::warn!("Auto-refresh of favorites skipped: {}", e);
+                }
+            }
+            Err(e) => log::warn!("Failed to list stale favorites: {}", e),
+        }
+    }
+}
+
+/// Favorites to refresh with the locale they were last cached in, oldest
+/// refresh first. `stale_hours` keeps only those not refreshed for that long.
+fn favorite_targets(
+    conn: &Connection,
+    item_ids: Option<&[i64]>,
+    stale_hours: Option<u32>,
+) -> AppResult<Vec<(i64, Locale)>> {
+    let mut sql = "SELECT f.item_id, c.locale FROM favorites f
+                   LEFT JOIN cached_items c ON c.id = f.item_id
+                   WHERE 1 = 1"
+        .to_string();
+    let ids = item_ids.unwrap_or_default();
+    if item_ids.is_some() {
+        sql.push_str(&format!(
+            " AND f.item_id IN ({})",
+            vec!["?"; ids.len()].join(",")
+        ));
+    }
+    if let Some(hours) = stale_hours {
+        sql.push_str(&format!(
+            " AND (f.refreshed_at IS NULL OR f.refreshed_at < datetime('now', '-{} hours'))",
+            hours
+        ));
+    }
+    sql.push_str(" ORDER BY f.refreshed_at IS NOT NULL, f.refreshed_at");
+
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map(params_from_iter(ids), |row| {
+            let locale: Option<String> = row.get(1)?;
+            Ok((
+                row.get(0)?,
+                locale.map(|l| Locale::from_code(&l)).unwrap_or_default(),
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+async fn run_refresh(app: AppHandle, job_id: u64, targets: Vec<(i64, Locale)>, slot: RefreshSlot) {
+    let client = app.state::<BoothClient>();
+    let db = app.state::<AppDatabase>();
+    let total = targets.len();
+    let mut summary = RefreshSummary {
+        job_id,
+        total,
+        updated: 0,
+        unchanged: 0,
+        failed: 0,
+    };
+
+    for (i, (item_id, locale)) in targets.into_iter().enumerate() {
+        let result = fetch_and_cache_item(&client, &db, item_id, locale, Priority::Background)
+            .await
+            .and_then(|item| apply_snapshot(&*db.conn()?, &item));
+        match result {
+            Ok(changes) if changes.is_empty() => summary.unchanged += 1,
+            Ok(_) => summary.updated += 1,
+            Err(e) => {
+                log::warn!("Failed to refresh favorite {}: {}", item_id, e);
+                summary.failed += 1;
+            }
+        }
+
+        let _ = app.emit(
+            "favorites-refresh-progress",
+            RefreshProgress {
+                job_id,
+                item_id,
+                completed: i + 1,
+                total,
+            },
+        );
+    }
+
+    // Free the slot first, so a refresh started on `done` isn't refused
+    drop(slot);
+    let _ = app.emit("favorites-refresh-done", summary);
+}
+
+/// Update a favorite's stored fields from a fresh fetch and log each change.
+/// Fields Booth didn't return keep their stored value. Returns the changes.
+/// Thumbnails are kept at search card size and compared by image, so the
+/// full-size image of an item detail doesn't count as a change.
+pub(crate) fn apply_snapshot(conn: &Connection, item: &BoothItem) -> AppResult<Vec<String>> {
+    let stored = conn
+        .query_row(
+            "SELECT name, price, thumbnail_url, category_name, shop_name
+             FROM favorites WHERE item_id = ?1",
+            params![item.id],
+            |row| {
+                Ok([
+                    row.get::<_, Option<String>>(0)?,
+                    row.get::<_, Option<i64>>(1)?.map(|p| p.to_string()),
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ])
+            },
+        )
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Favorite {}", item.id)))?;
+
+    let fresh = [
+        Some(item.name.clone()),
+        Some(item.price.to_string()),
+        item.images.first().map(|url| card_thumbnail(url)),
+        category_key(item.category_name.clone()),
+        item.shop_name.clone(),
+    ];
+    let fields = [
+        "name",
+        "price",
+        "thumbnail_url",
+        "category_name",
+        "shop_name",
+    ];
+
+    let mut changed = Vec::new();
+    for ((field, old), new) in fields.iter().zip(&stored).zip(&fresh) {
+        let same = match *field {
+            "thumbnail_url" => old.as_deref().map(card_thumbnail) == *new,
+            _ => old == new,
+        };
+        if new.is_some() && !same {
+            conn.execute(
+                "INSERT INTO favorite_changes (item_id, field, old_value, new_value)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![item.id, field, old, new],
+            )?;
+            changed.push(field.to_string());
+        }
+    }
+
+    conn.execute(
+        "UPDATE favorites SET
+            name = ?2,
+            price = ?3,
+            thumbnail_url = COALESCE(?4, thumbnail_url),
+            category_name = COALESCE(?5, category_name),
+            shop_name = COALESCE(?6, shop_name),
+            refreshed_at = datetime('now')
+         WHERE item_id = ?1",
+        params![item.id, item.name, item.price, fresh[2], fresh[3], fresh[4],],
+    )?;
+    Ok(changed)
+}
+
+fn read_favorite_changes(conn: &Connection, item_id: i64) -> AppResult<Vec<FavoriteChange>> {
+    let mut stmt = conn.prepare(
+        "SELECT item_id, field, old_value, new_value, changed_at FROM favorite_changes
+         WHERE item_id = ?1 ORDER BY changed_at DESC, id DESC",
+    )?;
+    let rows = stmt
+        .query_map(params![item_id], |row| {
+            Ok(FavoriteChange {
+                item_id: row.get(0)?,
+                field: row.get(1)?,
+                old_value: row.get(2)?,
+                new_value: row.get(3)?,
+                changed_at: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refresh_slot_is_freed_on_drop_and_unwind() {
+        let slot = RefreshSlot::acquire().expect("no refresh is running");
+        assert!(RefreshSlot::acquire().is_none());
+        drop(slot);
+
+        let panicked = std::panic::catch_unwind(|| {
+            let _slot = RefreshSlot::acquire().unwrap();
+            panic!("refresh job failed");
+        });
+        assert!(panicked.is_err());
+        assert!(RefreshSlot::acquire().is_some());
+    }
+
+    fn item(id: i64, name: &str, price: i64) -> BoothItem {
+        BoothItem {
+            id,
+            name: name.to_string(),
+            description: None,
+            price,
+            category_name: Some("3D Clothing".to_string()),
+            shop_name: Some("Kipfel Shop".to_string()),
+            url: format!("https://booth.pm/ja/items/{}", id),
+            images: vec![format!("https://booth.pximg.net/{}.jpg", id)],
+            tags: Vec::new(),
+            wish_lists_count: None,
+            variations: Vec::new(),
+            locale: Locale::Ja,
+        }
+    }
+
+    fn with_favorite() -> AppDatabase {
+        let db = AppDatabase::open_in_memory().unwrap();
+        db.conn()
+            .unwrap()
+            .execute(
+                "INSERT INTO favorites (item_id, name, price, thumbnail_url, category_name, shop_name)
+                 VALUES (1, 'ワンピース', 1500, 'https://booth.pximg.net/1.jpg', '3D衣装', 'Kipfel Shop')",
+                [],
+            )
+            .unwrap();
+        db
+    }
+
+    #[test]
+    fn unchanged_snapshot_records_nothing() {
+        let db = with_favorite();
+        let conn = db.conn().unwrap();
+        let changes = apply_snapshot(&conn, &item(1, "ワンピース", 1500)).unwrap();
+        assert!(changes.is_empty());
+        assert!(read_favorite_changes(&conn, 1).unwrap().is_empty());
+
+        let refreshed: Option<String> = conn
+            .query_row(
+                "SELECT refreshed_at FROM favorites WHERE item_id = 1",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert!(refreshed.is_some());
+    }
+
+    #[test]
+    fn changed_fields_are_updated_and_logged() {
+        let db = with_favorite();
+        let conn = db.conn().unwrap();
+        let mut fresh = item(1, "【セール】ワンピース", 1200);
+        fresh.shop_name = None;
+        let changes = apply_snapshot(&conn, &fresh).unwrap();
+        assert_eq!(changes, vec!["name", "price"]);
+
+        let (name, price, shop): (String, i64, String) = conn
+            .query_row(
+                "SELECT name, price, shop_name FROM favorites WHERE item_id = 1",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(name, "【セール】ワンピース");
+        assert_eq!(price, 1200);
+        // Missing in the fetch, so the stored shop stays
+        assert_eq!(shop, "Kipfel Shop");
+
+        let log = read_favorite_changes(&conn, 1).unwrap();
+        let price_change = log.iter().find(|c| c.field == "price").unwrap();
+        assert_eq!(price_change.old_value.as_deref(), Some("1500"));
+        assert_eq!(price_change.new_value.as_deref(), Some("1200"));
+    }
+
+    #[test]
+    fn search_card_thumbnail_matches_the_detail_image() {
+        let db = AppDatabase::open_in_memory().unwrap();
+        let conn = db.conn().unwrap();
+        let card = "https://booth.pximg.net/c/300x300_a2_g5/abc/i/5123456/aaa_base_resized.jpg";
+        conn.execute(
+            "INSERT INTO favorites (item_id, name, price, thumbnail_url, category_name, shop_name)
+             VALUES (1, 'ワンピース', 1500, ?1, '3D衣装', 'Kipfel Shop')",
+            params![card],
+        )
+        .unwrap();
+
+        // Item details carry the full-size original
+        let mut fresh = item(1, "ワンピース", 1500);
+        fresh.images = vec!["https://booth.pximg.net/abc/i/5123456/aaa_base_resized.jpg".into()];
+        assert!(apply_snapshot(&conn, &fresh).unwrap().is_empty());
+        assert!(read_favorite_changes(&conn, 1).unwrap().is_empty());
+        let stored: String = conn
+            .query_row(
+                "SELECT thumbnail_url FROM favorites WHERE item_id = 1",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(stored, card);
+
+        // A different image is still a change
+        fresh.images = vec!["https://booth.pximg.net/abc/i/5123456/new_base_resized.jpg".into()];
+        assert_eq!(
+            apply_snapshot(&conn, &fresh).unwrap(),
+            vec!["thumbnail_url"]
+        );
+    }
+
+    #[test]
+    fn unknown_favorite_is_not_found() {
+        let db = with_favorite();
+        let err = apply_snapshot(&db.conn().unwrap(), &item(2, "x", 0)).unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[test]
+    fn targets_skip_recently_refreshed_favorites() {
+        let db = with_favorite();
+        let conn = db.conn().unwrap();
+        conn.execute(
+            "INSERT INTO favorites (item_id, name, price, refreshed_at)
+             VALUES (2, 'fresh', 0, datetime('now', '-1 hours')),
+                    (3, 'old', 0, datetime('now', '-30 hours'))",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO cached_items (id, name, price, url, locale)
+             VALUES (3, 'old', 0, 'https://booth.pm/ko/items/3', 'ko')",
+            [],
+        )
+        .unwrap();
+
+        let ids =
+            |targets: Vec<(i64, Locale)>| targets.into_iter().map(|t| t.0).collect::<Vec<_>>();
+        assert_eq!(
+            ids(favorite_targets(&conn, None, None).unwrap()),
+            vec![1, 3, 2]
+        );
+        let stale = favorite_targets(&conn, None, Some(24)).unwrap();
+        assert_eq!(stale, vec![(1, Locale::Ja), (3, Locale::Ko)]);
+        assert_eq!(
+            ids(favorite_targets(&conn, Some(&[2]), None).unwrap()),
+            vec![2]
+        );
+    }
+}
@@ -12,7 +12,14 @@ pub struct DashboardStats {
     pub collections_count: i64,
     pub tags_count: i64,
     pub searches_count: i64,
+    /// Sum of the wishlist: favorites not marked as owned
     pub total_value: i64,
+    /// Like `total_value`, but counting the most expensive known variation of each item
+    pub total_value_max: i64,
+    pub owned_count: i64,
+    /// Sum of `price_paid` over owned items, falling back to the listed price
+    pub total_spent: i64,
+    /// Average price over the same wishlist as `total_value`
     pub avg_price: i64,
 }
 
@@ -83,12 +90,28 @@ pub fn get_all_statistics(db: State<'_, AppDatabase>) -> AppResult<AllStatistics
     let searches_count: i64 =
         conn.query_row("SELECT COUNT(*) FROM search_history", [], |row| row.get(0))?;
     let total_value: i64 = conn.query_row(
-        "SELECT COALESCE(SUM(price), 0) FROM favorites",
+        "SELECT COALESCE(SUM(price), 0) FROM favorites WHERE owned = 0",
         [],
         |row| row.get(0),
     )?;
+    let total_value_max: i64 = conn.query_row(
+        "SELECT COALESCE(SUM(COALESCE(v.max_price, f.price)), 0)
+         FROM favorites f
+         LEFT JOIN (
+             SELECT item_id, MAX(price) AS max_price FROM cached_item_variations GROUP BY item_id
+         ) v ON v.item_id = f.item_id
+         WHERE f.owned = 0",
+        [],
+        |row| row.get(0),
+    )?;
+    let (owned_count, total_spent): (i64, i64) = conn.query_row(
+        "SELECT COUNT(*), COALESCE(SUM(COALESCE(price_paid, price)), 0)
+         FROM favorites WHERE owned = 1",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
     let avg_price: i64 = conn.query_row(
-        "SELECT CAST(COALESCE(AVG(price), 0) AS INTEGER) FROM favorites",
+        "SELECT CAST(COALESCE(AVG(price), 0) AS INTEGER) FROM favorites WHERE owned = 0",
         [],
         |row| row.get(0),
     )?;
@@ -99,6 +122,9 @@ pub fn get_all_statistics(db: State<'_, AppDatabase>) -> AppResult<AllStatistics
         tags_count,
         searches_count,
         total_value,
+        total_value_max,
+        owned_count,
+        total_spent,
         avg_price,
     };
 
@@ -16,7 +16,17 @@ impl AppDatabase {
 
         let db_path = app_data_dir.join("boothhunter.db");
         let conn = Connection::open(&db_path)?;
+        Self::from_connection(conn)
+    }
+
+    /// Fresh database with the full schema, for tests.
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self, AppError> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
 
+    /// Apply pragmas and idempotent migrations to an open connection.
+    fn from_connection(conn: Connection) -> Result<Self, AppError> {
         conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA foreign_keys = ON;")?;
 
         // Idempotent migrations
@@ -130,9 +140,211 @@ impl AppDatabase {
             );",
         )?;
 
-        // Evict cached items older than 30 days to prevent unbounded growth
+        // Migration v6: remember when wish_count was fetched so fresh counts can be reused
+        let has_wish_count_at: bool = conn
+            .prepare("SELECT wish_count_at FROM cached_items LIMIT 0")
+            .is_ok();
+        if !has_wish_count_at {
+            conn.execute_batch("ALTER TABLE cached_items ADD COLUMN wish_count_at TEXT;")?;
+        }
+
+        // Migration v7: per-item variations (tiers, digital vs physical)
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS cached_item_variations (
+                item_id   INTEGER NOT NULL REFERENCES cached_items(id) ON DELETE CASCADE,
+                position  INTEGER NOT NULL,
+                name      TEXT,
+                price     INTEGER NOT NULL,
+                kind      TEXT NOT NULL,
+                stock     TEXT NOT NULL,
+                PRIMARY KEY (item_id, position)
+            );",
+        )?;
+
+        // Migration v8: shop metadata keyed by subdomain (favorites only keep a display name)
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS shops (
+                subdomain   TEXT PRIMARY KEY,
+                name        TEXT NOT NULL,
+                icon_url    TEXT,
+                description TEXT,
+                item_count  INTEGER,
+                cached_at   TEXT NOT NULL DEFAULT (datetime('now'))
+            );",
+        )?;
+
+        // Migration v9: Booth locale each cached row was fetched from
+        let has_locale: bool = conn
+            .prepare("SELECT locale FROM cached_items LIMIT 0")
+            .is_ok();
+        if !has_locale {
+            conn.execute_batch(
+                "ALTER TABLE cached_items ADD COLUMN locale TEXT NOT NULL DEFAULT 'ja';",
+            )?;
+        }
+
+        // Migration v10: when the row last came from a full detail fetch (vs. a search card)
+        let has_detail_cached_at: bool = conn
+            .prepare("SELECT detail_cached_at FROM cached_items LIMIT 0")
+            .is_ok();
+        if !has_detail_cached_at {
+            conn.execute_batch("ALTER TABLE cached_items ADD COLUMN detail_cached_at TEXT;")?;
+        }
+
+        // Migration v11: downloaded images served through the boothimg:// scheme
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS image_cache (
+                url          TEXT PRIMARY KEY,
+                file_name    TEXT NOT NULL,
+                content_type TEXT NOT NULL,
+                size         INTEGER NOT NULL,
+                last_used_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            CREATE INDEX IF NOT EXISTS idx_image_cache_last_used ON image_cache(last_used_at);",
+        )?;
+
+        // Migration v12: when a favorite's note was last edited
+        let has_note_updated_at: bool = conn
+            .prepare("SELECT note_updated_at FROM favorites LIMIT 0")
+            .is_ok();
+        if !has_note_updated_at {
+            conn.execute_batch("ALTER TABLE favorites ADD COLUMN note_updated_at TEXT;")?;
+        }
+
+        // Migration v13: observed prices of favorites, one row per change
+        let has_price_history: bool = conn.prepare("SELECT 1 FROM price_history LIMIT 0").is_ok();
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS price_history (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                item_id     INTEGER NOT NULL,
+                price       INTEGER NOT NULL,
+                observed_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            CREATE INDEX IF NOT EXISTS idx_price_history_item ON price_history(item_id, observed_at);",
+        )?;
+        if !has_price_history {
+            // Existing favorites start from their saved price, not whatever the
+            // first refresh sees, so an ongoing sale still counts as a drop
+            conn.execute(
+                "INSERT INTO price_history (item_id, price, observed_at)
+                 SELECT item_id, price, COALESCE(added_at, datetime('now')) FROM favorites
+                 WHERE item_id NOT IN (SELECT item_id FROM price_history)",
+                [],
+            )?;
+        }
+
+        // Migration v14: favorite snapshot refreshes and the field changes they found
+        let has_refreshed_at: bool = conn
+            .prepare("SELECT refreshed_at FROM favorites LIMIT 0")
+            .is_ok();
+        if !has_refreshed_at {
+            conn.execute_batch("ALTER TABLE favorites ADD COLUMN refreshed_at TEXT;")?;
+        }
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS favorite_changes (
+                id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                item_id    INTEGER NOT NULL,
+                field      TEXT NOT NULL,
+                old_value  TEXT,
+                new_value  TEXT,
+                changed_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            CREATE INDEX IF NOT EXISTS idx_favorite_changes_item ON favorite_changes(item_id);",
+        )?;
+
+        // Migration v15: whether each favorite is still on Booth
+        let has_availability: bool = conn
+            .prepare("SELECT availability FROM favorites LIMIT 0")
+            .is_ok();
+        if !has_availability {
+            conn.execute_batch(
+                "ALTER TABLE favorites ADD COLUMN availability TEXT NOT NULL DEFAULT 'unknown';
+                 ALTER TABLE favorites ADD COLUMN last_checked_at TEXT;",
+            )?;
+        }
+
+        // Migration v16: ownership, so favorites double as a purchase record
+        let has_owned: bool = conn.prepare("SELECT owned FROM favorites LIMIT 0").is_ok();
+        if !has_owned {
+            conn.execute_batch(
+                "ALTER TABLE favorites ADD COLUMN owned INTEGER NOT NULL DEFAULT 0;
+                 ALTER TABLE favorites ADD COLUMN purchased_at TEXT;
+                 ALTER TABLE favorites ADD COLUMN price_paid INTEGER;
+                 ALTER TABLE favorites ADD COLUMN purchased_variation TEXT;
+                 ALTER TABLE favorites ADD COLUMN order_ref TEXT;",
+            )?;
+        }
+
+        // Migration v17: trigram full-text indexes, kept in sync by triggers.
+        // `favorites_fts` rows are keyed by item id and carry the item's user tags.
+        let has_fts: bool = conn.prepare("SELECT 1 FROM favorites_fts LIMIT 0").is_ok();
+        if !has_fts {
+            // All or nothing: a half-created index would never be completed
+            let tx = conn.unchecked_transaction()?;
+            tx.execute_batch(
+                "CREATE VIRTUAL TABLE favorites_fts USING fts5(
+                    name, shop_name, note, tags,
+                    tokenize = 'trigram'
+                );
+                CREATE VIRTUAL TABLE cached_items_fts USING fts5(
+                    name, description, shop_name, tags_json,
+                    content = 'cached_items', content_rowid = 'id',
+                    tokenize = 'trigram'
+                );
+
+                CREATE TRIGGER favorites_fts_insert AFTER INSERT ON favorites BEGIN
+                    INSERT INTO favorites_fts (rowid, name, shop_name, note, tags)
+                    VALUES (new.item_id, new.name, new.shop_name, new.note,
+                            (SELECT group_concat(tag, ' ') FROM item_tags WHERE item_id = new.item_id));
+                END;
+                CREATE TRIGGER favorites_fts_update AFTER UPDATE OF name, shop_name, note ON favorites BEGIN
+                    UPDATE favorites_fts SET name = new.name, shop_name = new.shop_name, note = new.note
+                    WHERE rowid = new.item_id;
+                END;
+                CREATE TRIGGER favorites_fts_delete AFTER DELETE ON favorites BEGIN
+                    DELETE FROM favorites_fts WHERE rowid = old.item_id;
+                END;
+                CREATE TRIGGER item_tags_fts_insert AFTER INSERT ON item_tags BEGIN
+                    UPDATE favorites_fts
+                    SET tags = (SELECT group_concat(tag, ' ') FROM item_tags WHERE item_id = new.item_id)
+                    WHERE rowid = new.item_id;
+                END;
+                CREATE TRIGGER item_tags_fts_delete AFTER DELETE ON item_tags BEGIN
+                    UPDATE favorites_fts
+                    SET tags = (SELECT group_concat(tag, ' ') FROM item_tags WHERE item_id = old.item_id)
+                    WHERE rowid = old.item_id;
+                END;
+
+                CREATE TRIGGER cached_items_fts_insert AFTER INSERT ON cached_items BEGIN
+                    INSERT INTO cached_items_fts (rowid, name, description, shop_name, tags_json)
+                    VALUES (new.id, new.name, new.description, new.shop_name, new.tags_json);
+                END;
+                CREATE TRIGGER cached_items_fts_update
+                AFTER UPDATE OF name, description, shop_name, tags_json ON cached_items BEGIN
+                    INSERT INTO cached_items_fts (cached_items_fts, rowid, name, description, shop_name, tags_json)
+                    VALUES ('delete', old.id, old.name, old.description, old.shop_name, old.tags_json);
+                    INSERT INTO cached_items_fts (rowid, name, description, shop_name, tags_json)
+                    VALUES (new.id, new.name, new.description, new.shop_name, new.tags_json);
+                END;
+                CREATE TRIGGER cached_items_fts_delete AFTER DELETE ON cached_items BEGIN
+                    INSERT INTO cached_items_fts (cached_items_fts, rowid, name, description, shop_name, tags_json)
+                    VALUES ('delete', old.id, old.name, old.description, old.shop_name, old.tags_json);
+                END;
+
+                INSERT INTO favorites_fts (rowid, name, shop_name, note, tags)
+                SELECT f.item_id, f.name, f.shop_name, f.note,
+                       (SELECT group_concat(tag, ' ') FROM item_tags WHERE item_id = f.item_id)
+                FROM favorites f;
+                INSERT INTO cached_items_fts (cached_items_fts) VALUES ('rebuild');",
+            )?;
+            tx.commit()?;
+        }
+
+        // Evict cached items older than 30 days to prevent unbounded growth.
+        // Favorites are kept: they may be the last copy of a delisted item.
         conn.execute(
-            "DELETE FROM cached_items WHERE cached_at < datetime('now', '-30 days')",
+            "DELETE FROM cached_items WHERE cached_at < datetime('now', '-30 days')
+               AND id NOT IN (SELECT item_id FROM favorites)",
             [],
         )?;
 
@@ -192,3 +404,58 @@ impl AppDatabase {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::booth::models::BoothItem;
+    use crate::commands::db::{read_favorites, write_cached_items, FavoritesFilter};
+
+    #[test]
+    fn price_history_is_seeded_from_existing_favorites() {
+        let path =
+            std::env::temp_dir().join(format!("boothhunter-migration-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        // A database from before v13: favorites but no price history
+        {
+            let db = AppDatabase::from_connection(Connection::open(&path).unwrap()).unwrap();
+            db.conn()
+                .unwrap()
+                .execute_batch(
+                    "DROP TABLE price_history;
+                     INSERT INTO favorites (item_id, name, price, added_at)
+                     VALUES (1, 'ワンピース', 1500, '2025-01-01 00:00:00');",
+                )
+                .unwrap();
+        }
+
+        let db = AppDatabase::from_connection(Connection::open(&path).unwrap()).unwrap();
+        let mut conn = db.conn_mut().unwrap();
+        // The first refresh after the upgrade already sees the sale price
+        let on_sale = BoothItem {
+            id: 1,
+            name: "ワンピース".to_string(),
+            description: None,
+            price: 1200,
+            category_name: None,
+            shop_name: None,
+            url: "https://booth.pm/ja/items/1".to_string(),
+            images: vec![],
+            tags: vec![],
+            wish_lists_count: None,
+            variations: vec![],
+            locale: Default::default(),
+        };
+        write_cached_items(&mut conn, &[on_sale], false).unwrap();
+
+        let favorite = &read_favorites(&conn, &FavoritesFilter::default()).unwrap()[0];
+        assert_eq!(favorite.added_price, 1500);
+        assert_eq!(favorite.current_price, Some(1200));
+        assert!(favorite.price_dropped);
+
+        drop(conn);
+        drop(db);
+        let _ = std::fs::remove_file(&path);
+    }
+}
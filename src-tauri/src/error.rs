@@ -1,6 +1,7 @@
+use serde::ser::SerializeStruct;
 use serde::Serialize;
 
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum AppError {
     #[error("{0}")]
     ParseError(String),
@@ -8,8 +9,78 @@ pub enum AppError {
     #[error("Item not found: {0}")]
     NotFound(String),
 
+    /// Booth redirected away from an item page, as it does for private items
+    #[error("Item is private: {0}")]
+    Private(String),
+
     #[error("Database error: {0}")]
     Database(String),
+
+    /// Local file reads and writes, such as the image cache
+    #[error("File error: {0}")]
+    Io(String),
+
+    /// Connection, DNS, TLS or timeout failures before a response arrived
+    #[error("Network error: {0}")]
+    Network(String),
+
+    /// Booth answered with an unexpected status
+    #[error("Booth.pm returned {status} for {url}")]
+    HttpStatus { status: u16, url: String },
+
+    #[error("Rate limited by Booth.pm")]
+    RateLimited { retry_after_secs: Option<u64> },
+
+    /// Bad input from the caller; retrying the same request won't help
+    #[error("{0}")]
+    Validation(String),
+
+    #[error("Cancelled")]
+    Cancelled,
+
+    /// A job of the same kind is already running; try again once it ends
+    #[error("{0}")]
+    Busy(String),
+}
+
+impl AppError {
+    /// Stable identifier the frontend can localize and branch on.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::ParseError(_) => "parse_error",
+            AppError::NotFound(_) => "not_found",
+            AppError::Private(_) => "private",
+            AppError::Database(_) => "database",
+            AppError::Io(_) => "io",
+            AppError::Network(_) => "network",
+            AppError::HttpStatus { .. } => "http_status",
+            AppError::RateLimited { .. } => "rate_limited",
+            AppError::Validation(_) => "validation",
+            AppError::Cancelled => "cancelled",
+            AppError::Busy(_) => "busy",
+        }
+    }
+
+    /// Whether the same request may succeed if sent again later.
+    pub fn retryable(&self) -> bool {
+        match self {
+            AppError::Network(_) | AppError::RateLimited { .. } | AppError::Busy(_) => true,
+            AppError::HttpStatus { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+
+    fn details(&self) -> Option<serde_json::Value> {
+        match self {
+            AppError::HttpStatus { status, url } => {
+                Some(serde_json::json!({ "status": status, "url": url }))
+            }
+            AppError::RateLimited { retry_after_secs } => {
+                Some(serde_json::json!({ "retry_after_secs": retry_after_secs }))
+            }
+            _ => None,
+        }
+    }
 }
 
 impl From<rusqlite::Error> for AppError {
@@ -18,13 +89,68 @@ impl From<rusqlite::Error> for AppError {
     }
 }
 
+/// Serialized as `{ code, message, retryable, details }` so the UI never has
+/// to match on English message text.
 impl Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        let mut s = serializer.serialize_struct("AppError", 4)?;
+        s.serialize_field("code", self.code())?;
+        s.serialize_field("message", &self.to_string())?;
+        s.serialize_field("retryable", &self.retryable())?;
+        s.serialize_field("details", &self.details())?;
+        s.end()
     }
 }
 
 pub type AppResult<T> = Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_code_message_retryable_and_details() {
+        let err = AppError::HttpStatus {
+            status: 503,
+            url: "https://booth.pm/ja/items/1".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({
+                "code": "http_status",
+                "message": "Booth.pm returned 503 for https://booth.pm/ja/items/1",
+                "retryable": true,
+                "details": { "status": 503, "url": "https://booth.pm/ja/items/1" },
+            })
+        );
+    }
+
+    #[test]
+    fn variants_without_details_serialize_null() {
+        let value = serde_json::to_value(AppError::Validation("Name too long".into())).unwrap();
+        assert_eq!(value["code"], "validation");
+        assert_eq!(value["retryable"], false);
+        assert!(value["details"].is_null());
+
+        let value = serde_json::to_value(AppError::Cancelled).unwrap();
+        assert_eq!(value["code"], "cancelled");
+    }
+
+    #[test]
+    fn retryable_classification() {
+        assert!(AppError::Network("timeout".into()).retryable());
+        assert!(AppError::RateLimited {
+            retry_after_secs: Some(30)
+        }
+        .retryable());
+        assert!(!AppError::HttpStatus {
+            status: 403,
+            url: String::new()
+        }
+        .retryable());
+        assert!(!AppError::NotFound("x".into()).retryable());
+    }
+}
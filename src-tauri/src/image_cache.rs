@@ -0,0 +1,478 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use percent_encoding::percent_decode_str;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{AppHandle, Manager, UriSchemeResponder};
+use tauri_plugin_http::reqwest;
+
+use crate::database::AppDatabase;
+use crate::error::{AppError, AppResult};
+
+/// Custom URI scheme the webview loads cached images from:
+/// `boothimg://localhost/<encodeURIComponent(original url)>`
+pub const SCHEME: &str = "boothimg";
+/// Total size of cached image files before least-recently-used ones are evicted
+const DEFAULT_MAX_BYTES: u64 = 200 * 1024 * 1024;
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+/// Recently cached items whose thumbnails are downloaded at startup
+const RECENT_ITEM_THUMBNAILS: i64 = 300;
+
+// ── Types ──────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct ImageCacheUsage {
+    pub file_count: i64,
+    pub total_bytes: i64,
+    pub max_bytes: u64,
+}
+
+pub struct CachedImage {
+    pub bytes: Vec<u8>,
+    pub content_type: String,
+}
+
+// ── Cache ──────────────────────────────────────────────
+
+/// Image files under `{app_data_dir}/images`, indexed by the `image_cache` table.
+pub struct ImageCache {
+    dir: PathBuf,
+    http: reqwest::Client,
+    max_bytes: u64,
+}
+
+impl ImageCache {
+    pub fn new(app_data_dir: &Path) -> AppResult<Self> {
+        Self::with_limit(app_data_dir.join("images"), DEFAULT_MAX_BYTES)
+    }
+
+    fn with_limit(dir: PathBuf, max_bytes: u64) -> AppResult<Self> {
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| AppError::Io(format!("Failed to create image dir: {}", e)))?;
+        let http = reqwest::Client::builder()
+            .timeout(DOWNLOAD_TIMEOUT)
+            .build()
+            .map_err(|e| AppError::Network(format!("Failed to build HTTP client: {}", e)))?;
+        Ok(Self {
+            dir,
+            http,
+            max_bytes,
+        })
+    }
+
+    /// Serve an image from disk, downloading and caching it on a miss.
+    pub async fn fetch(&self, db: &AppDatabase, url: &str) -> AppResult<CachedImage> {
+        validate_image_url(url)?;
+        if let Some(image) = self.lookup(db, url)? {
+            return Ok(image);
+        }
+
+        let resp = self
+            .http
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| AppError::Network(e.to_string()))?;
+        let status = resp.status();
+        if !status.is_success() {
+            return Err(AppError::HttpStatus {
+                status: status.as_u16(),
+                url: url.to_string(),
+            });
+        }
+        let content_type = resp
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("image/jpeg")
+            .to_string();
+        let bytes = resp
+            .bytes()
+            .await
+            .map_err(|e| AppError::Network(e.to_string()))?
+            .to_vec();
+
+        self.store(db, url, &bytes, &content_type)?;
+        Ok(CachedImage {
+            bytes,
+            content_type,
+        })
+    }
+
+    // Disk I/O happens with the connection released: every DB command waits
+    // on it, and thumbnails are served all the time.
+
+    fn lookup(&self, db: &AppDatabase, url: &str) -> AppResult<Option<CachedImage>> {
+        let row: Option<(String, String)> = db
+            .conn()?
+            .query_row(
+                "SELECT file_name, content_type FROM image_cache WHERE url = ?1",
+                params![url],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((file_name, content_type)) = row else {
+            return Ok(None);
+        };
+
+        let read = std::fs::read(self.dir.join(&file_name));
+        let conn = db.conn()?;
+        match read {
+            Ok(bytes) => {
+                conn.execute(
+                    "UPDATE image_cache SET last_used_at = datetime('now') WHERE url = ?1",
+                    params![url],
+                )?;
+                Ok(Some(CachedImage {
+                    bytes,
+                    content_type,
+                }))
+            }
+            Err(e) => {
+                // File removed behind our back: forget the row and download again
+                log::warn!("Cached image {} is unreadable: {}", file_name, e);
+                conn.execute("DELETE FROM image_cache WHERE url = ?1", params![url])?;
+                Ok(None)
+            }
+        }
+    }
+
+    fn store(
+        &self,
+        db: &AppDatabase,
+        url: &str,
+        bytes: &[u8],
+        content_type: &str,
+    ) -> AppResult<()> {
+        let file_name = file_name_for(url);
+        std::fs::write(self.dir.join(&file_name), bytes)
+            .map_err(|e| AppError::Io(format!("Failed to write cached image: {}", e)))?;
+
+        let evicted = {
+            let conn = db.conn()?;
+            conn.execute(
+                "INSERT OR REPLACE INTO image_cache (url, file_name, content_type, size, last_used_at)
+                 VALUES (?1, ?2, ?3, ?4, datetime('now'))",
+                params![url, file_name, content_type, bytes.len() as i64],
+            )?;
+            self.evict(&conn)?
+        };
+        for file_name in evicted {
+            if let Err(e) = std::fs::remove_file(self.dir.join(&file_name)) {
+                log::warn!("Failed to remove cached image {}: {}", file_name, e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Forget least-recently-used images until the cache fits in `max_bytes`,
+    /// returning the file names to delete. Favorite thumbnails go last so
+    /// favorites keep working offline.
+    fn evict(&self, conn: &Connection) -> AppResult<Vec<String>> {
+        let total: i64 = conn.query_row(
+            "SELECT COALESCE(SUM(size), 0) FROM image_cache",
+            [],
+            |row| row.get(0),
+        )?;
+        let mut excess = total - self.max_bytes as i64;
+        if excess <= 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT url, file_name, size FROM image_cache
+             ORDER BY url IN (SELECT thumbnail_url FROM favorites WHERE thumbnail_url IS NOT NULL),
+                      last_used_at, rowid",
+        )?;
+        let victims = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut evicted = Vec::new();
+        for (url, file_name, size) in victims {
+            if excess <= 0 {
+                break;
+            }
+            conn.execute("DELETE FROM image_cache WHERE url = ?1", params![url])?;
+            evicted.push(file_name);
+            excess -= size;
+        }
+        Ok(evicted)
+    }
+
+    pub fn usage(&self, db: &AppDatabase) -> AppResult<ImageCacheUsage> {
+        let conn = db.conn()?;
+        let (file_count, total_bytes) = conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(size), 0) FROM image_cache",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(ImageCacheUsage {
+            file_count,
+            total_bytes,
+            max_bytes: self.max_bytes,
+        })
+    }
+
+    pub fn clear(&self, db: &AppDatabase) -> AppResult<()> {
+        db.conn()?.execute("DELETE FROM image_cache", [])?;
+        let entries = std::fs::read_dir(&self.dir)
+            .map_err(|e| AppError::Io(format!("Failed to read image dir: {}", e)))?;
+        for entry in entries.flatten() {
+            if let Err(e) = std::fs::remove_file(entry.path()) {
+                log::warn!("Failed to remove {}: {}", entry.path().display(), e);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Only Booth's image CDN may be proxied, so the scheme can't be used to fetch
+/// arbitrary URLs.
+fn validate_image_url(url: &str) -> AppResult<()> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|_| AppError::Validation(format!("Invalid image URL: {}", url)))?;
+    let host_ok = parsed
+        .host_str()
+        .is_some_and(|h| h == "pximg.net" || h.ends_with(".pximg.net"));
+    if parsed.scheme() != "https" || !host_ok {
+        return Err(AppError::Validation(format!(
+            "Not a Booth image URL: {}",
+            url
+        )));
+    }
+    Ok(())
+}
+
+/// Stable file name for a URL (FNV-1a), keeping the extension for easier debugging.
+fn file_name_for(url: &str) -> String {
+    let hash = url.bytes().fold(0xcbf29ce484222325u64, |h, b| {
+        (h ^ u64::from(b)).wrapping_mul(0x100000001b3)
+    });
+    let ext = url
+        .rsplit('/')
+        .next()
+        .and_then(|last| last.split('?').next())
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, ext)| ext)
+        .filter(|ext| {
+            !ext.is_empty() && ext.len() <= 4 && ext.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+        .unwrap_or("img");
+    format!("{:016x}.{}", hash, ext)
+}
+
+// ── URI scheme ─────────────────────────────────────────
+
+/// Handler for `boothimg://localhost/<encoded url>`.
+pub fn handle_request(app: AppHandle, request: Request<Vec<u8>>, responder: UriSchemeResponder) {
+    let encoded = request.uri().path().trim_start_matches('/').to_string();
+    tauri::async_runtime::spawn(async move {
+        let url = percent_decode_str(&encoded)
+            .decode_utf8_lossy()
+            .into_owned();
+        let cache = app.state::<ImageCache>();
+        let db = app.state::<AppDatabase>();
+
+        let response = match cache.fetch(&db, &url).await {
+            Ok(image) => Response::builder()
+                .status(StatusCode::OK)
+                .header(header::CONTENT_TYPE, image.content_type)
+                .header(header::CACHE_CONTROL, "max-age=86400")
+                .body(image.bytes),
+            Err(e) => {
+                log::warn!("Image cache failed for {}: {}", url, e);
+                let status = match e {
+                    AppError::Validation(_) => StatusCode::BAD_REQUEST,
+                    _ => StatusCode::BAD_GATEWAY,
+                };
+                Response::builder().status(status).body(Vec::new())
+            }
+        };
+        match response {
+            Ok(response) => responder.respond(response),
+            Err(e) => log::error!("Failed to build image response: {}", e),
+        }
+    });
+}
+
+/// Download thumbnails that aren't cached yet, so favorites and offline search
+/// results render without a connection.
+pub async fn warm_offline_thumbnails(app: AppHandle) {
+    let cache = app.state::<ImageCache>();
+    let db = app.state::<AppDatabase>();
+
+    let urls = match db.conn().and_then(|conn| thumbnails_to_warm(&conn)) {
+        Ok(urls) => urls,
+        Err(e) => {
+            log::warn!("Failed to list thumbnails to cache: {}", e);
+            return;
+        }
+    };
+
+    for url in urls {
+        if let Err(e) = cache.fetch(&db, &url).await {
+            log::warn!("Failed to cache thumbnail {}: {}", url, e);
+        }
+    }
+}
+
+/// Favorite thumbnails, then the first image of the most recently cached items
+/// (what an offline search card shows). The recent items are capped so warming
+/// stays well inside the cache size.
+fn thumbnails_to_warm(conn: &Connection) -> AppResult<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT url FROM (
+             SELECT thumbnail_url AS url, 0 AS pass, 0 AS rank FROM favorites
+             UNION ALL
+             SELECT * FROM (
+                 SELECT json_extract(images_json, '$[0]'), 1, -id FROM cached_items
+                 ORDER BY cached_at DESC, id DESC
+                 LIMIT ?1
+             )
+         )
+         WHERE url IS NOT NULL AND url NOT IN (SELECT url FROM image_cache)
+         GROUP BY url
+         ORDER BY MIN(pass), MIN(rank)",
+    )?;
+    let urls = stmt
+        .query_map(params![RECENT_ITEM_THUMBNAILS], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(urls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache(max_bytes: u64) -> (ImageCache, AppDatabase) {
+        let dir = std::env::temp_dir().join(format!(
+            "boothhunter-images-{}-{}",
+            std::process::id(),
+            max_bytes
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let cache = ImageCache::with_limit(dir, max_bytes).unwrap();
+        (cache, AppDatabase::open_in_memory().unwrap())
+    }
+
+    #[test]
+    fn only_pximg_https_urls_are_accepted() {
+        assert!(validate_image_url("https://booth.pximg.net/c/300x300/abc/i/1/a.jpg").is_ok());
+        assert!(validate_image_url("https://s2.booth.pximg.net/abc.png").is_ok());
+        assert!(validate_image_url("http://booth.pximg.net/a.jpg").is_err());
+        assert!(validate_image_url("https://evil.com/booth.pximg.net/a.jpg").is_err());
+        assert!(validate_image_url("https://pximg.net.evil.com/a.jpg").is_err());
+        assert!(validate_image_url("file:///etc/passwd").is_err());
+    }
+
+    #[test]
+    fn file_names_are_stable_and_keep_the_extension() {
+        let a = file_name_for("https://booth.pximg.net/abc/i/1/aaa_base_resized.jpg");
+        assert_eq!(
+            a,
+            file_name_for("https://booth.pximg.net/abc/i/1/aaa_base_resized.jpg")
+        );
+        assert!(a.ends_with(".jpg"));
+        assert_ne!(
+            a,
+            file_name_for("https://booth.pximg.net/abc/i/2/aaa_base_resized.jpg")
+        );
+        assert!(file_name_for("https://booth.pximg.net/noext").ends_with(".img"));
+    }
+
+    #[test]
+    fn evicts_least_recently_used_but_keeps_favorites() {
+        let (cache, db) = temp_cache(25);
+        let url = |n: u32| format!("https://booth.pximg.net/{}.jpg", n);
+
+        db.conn()
+            .unwrap()
+            .execute(
+                "INSERT INTO favorites (item_id, name, price, thumbnail_url) VALUES (1, 'fav', 0, ?1)",
+                params![url(1)],
+            )
+            .unwrap();
+        for n in 1..=3 {
+            cache.store(&db, &url(n), &[0u8; 10], "image/jpeg").unwrap();
+        }
+
+        // 30 bytes > 25: the oldest non-favorite (2) goes, the favorite (1) stays
+        let conn = db.conn().unwrap();
+        let mut stmt = conn
+            .prepare("SELECT url FROM image_cache ORDER BY url")
+            .unwrap();
+        let left: Vec<String> = stmt
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(left, vec![url(1), url(3)]);
+        assert!(!cache.dir.join(file_name_for(&url(2))).exists());
+        drop(stmt);
+        drop(conn);
+
+        let usage = cache.usage(&db).unwrap();
+        assert_eq!(usage.file_count, 2);
+        assert_eq!(usage.total_bytes, 20);
+
+        cache.clear(&db).unwrap();
+        assert_eq!(cache.usage(&db).unwrap().file_count, 0);
+        assert_eq!(std::fs::read_dir(&cache.dir).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn warms_favorites_then_recent_items() {
+        let (cache, db) = temp_cache(1024);
+        let url = |n: u32| format!("https://booth.pximg.net/{}.jpg", n);
+        {
+            let conn = db.conn().unwrap();
+            conn.execute(
+                "INSERT INTO favorites (item_id, name, price, thumbnail_url) VALUES (1, 'fav', 0, ?1)",
+                params![url(1)],
+            )
+            .unwrap();
+            let mut insert = conn
+                .prepare(
+                    "INSERT INTO cached_items (id, name, price, url, images_json, cached_at)
+                     VALUES (?1, 'item', 0, '', ?2, ?3)",
+                )
+                .unwrap();
+            for (id, images, cached_at) in [
+                (1, format!("[\"{}\"]", url(1)), "2026-01-03"),
+                (3, format!("[\"{}\"]", url(3)), "2026-01-01"),
+                (4, format!("[\"{}\", \"{}\"]", url(4), url(5)), "2026-01-02"),
+                (6, "[]".to_string(), "2026-01-04"),
+            ] {
+                insert.execute(params![id, images, cached_at]).unwrap();
+            }
+        }
+        cache.store(&db, &url(3), &[0u8; 10], "image/jpeg").unwrap();
+
+        // Already cached (3), non-first (5) and missing images are skipped
+        let urls = thumbnails_to_warm(&db.conn().unwrap()).unwrap();
+        assert_eq!(urls, vec![url(1), url(4)]);
+    }
+
+    #[test]
+    fn lookup_serves_stored_bytes() {
+        let (cache, db) = temp_cache(1024);
+        let url = "https://booth.pximg.net/x/y.png";
+        cache.store(&db, url, b"png-bytes", "image/png").unwrap();
+        let image = cache.lookup(&db, url).unwrap().expect("should be cached");
+        assert_eq!(image.bytes, b"png-bytes");
+        assert_eq!(image.content_type, "image/png");
+
+        // A file deleted on disk turns into a miss instead of an error
+        std::fs::remove_file(cache.dir.join(file_name_for(url))).unwrap();
+        assert!(cache.lookup(&db, url).unwrap().is_none());
+    }
+}
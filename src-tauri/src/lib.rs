@@ -2,12 +2,15 @@ mod booth;
 mod commands;
 mod database;
 mod error;
+mod image_cache;
 
 use tauri::{Emitter, Manager};
 use tauri_plugin_updater::UpdaterExt;
 
+use booth::client::BoothClient;
 use commands::updater::{PendingUpdate, UpdateInfo};
 use database::AppDatabase;
+use image_cache::ImageCache;
 
 async fn check_for_update(app: tauri::AppHandle) {
     let updater = match app.updater() {
@@ -51,15 +54,42 @@ pub fn run() {
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_process::init())
         .manage(PendingUpdate::default())
+        .register_asynchronous_uri_scheme_protocol(
+            image_cache::SCHEME,
+            |ctx, request, responder| {
+                image_cache::handle_request(ctx.app_handle().clone(), request, responder)
+            },
+        )
         .invoke_handler(tauri::generate_handler![
+            commands::booth::search_booth,
+            commands::booth::get_booth_item,
+            commands::booth::get_cached_item,
+            commands::booth::get_shop_items,
+            commands::crawl::crawl_search,
+            commands::crawl::cancel_search_crawl,
             commands::db::cache_items,
+            commands::db::search_cached_items,
             commands::db::save_search_history,
             commands::db::get_favorites,
+            commands::db::query_favorites,
+            commands::full_text::full_text_search,
+            commands::bulk::add_favorites_bulk,
+            commands::bulk::remove_favorites_bulk,
+            commands::bulk::add_tags_bulk,
+            commands::bulk::remove_tags_bulk,
+            commands::bulk::move_items_to_collection,
+            commands::importer::import_items,
+            commands::export::export_favorites,
             commands::db::add_favorite,
             commands::db::remove_favorite,
+            commands::db::set_favorite_note,
+            commands::db::mark_owned,
+            commands::db::unmark_owned,
+            commands::db::get_price_history,
             commands::db::get_popular_avatars,
             commands::db::check_avatars_need_update,
             commands::db::update_popular_avatar,
+            commands::enrich::start_wish_count_job,
             commands::collections::get_collections,
             commands::collections::create_collection,
             commands::collections::rename_collection,
@@ -74,6 +104,11 @@ pub fn run() {
             commands::collections::get_all_user_tags,
             commands::collections::get_all_item_tags_batch,
             commands::collections::get_all_item_collections_batch,
+            commands::images::get_image_cache_usage,
+            commands::images::clear_image_cache,
+            commands::refresh::refresh_favorites,
+            commands::refresh::set_favorites_auto_refresh,
+            commands::refresh::get_favorite_changes,
             commands::stats::get_all_statistics,
             commands::translation::get_cached_translation,
             commands::translation::save_cached_translation,
@@ -82,9 +117,23 @@ pub fn run() {
         .setup(|app| {
             // Initialize database
             let app_data_dir = app.path().app_data_dir()?;
+            let images = ImageCache::new(&app_data_dir)
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
             let db = AppDatabase::initialize(app_data_dir)
                 .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
             app.manage(db);
+            app.manage(images);
+
+            let client = BoothClient::new()
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+            app.manage(client);
+
+            // Fetch favorite and recent item thumbnails that aren't on disk yet
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(image_cache::warm_offline_thumbnails(handle));
+
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(commands::refresh::auto_refresh_loop(handle));
 
             #[cfg(desktop)]
             {
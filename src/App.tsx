@@ -2,6 +2,8 @@ import { useEffect, useState } from 'react';
 import { BrowserRouter, Routes, Route } from 'react-router-dom';
 import { QueryClient, QueryClientProvider } from '@tanstack/react-query';
 import { listen } from '@tauri-apps/api/event';
+import { onFavoritesRefreshed } from './lib/booth-api';
+import { applyStoredAutoRefresh } from './hooks/useFavoritesRefresh';
 import { SearchProvider } from './lib/SearchContext';
 import { I18nProvider } from './lib/i18n';
 import { ToastProvider } from './lib/ToastContext';
@@ -43,6 +45,19 @@ function App() {
     };
   }, []);
 
+  useEffect(() => {
+    applyStoredAutoRefresh();
+    // Manual and periodic refreshes both rewrite stored favorite fields
+    const unlisten = onFavoritesRefreshed(() => {
+      queryClient.invalidateQueries({ queryKey: ['favorites'] });
+      queryClient.invalidateQueries({ queryKey: ['collection-items'] });
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   return (
     <QueryClientProvider client={queryClient}>
       <I18nProvider>
@@ -1,6 +1,6 @@
 import { memo, useCallback } from 'react';
 import { Link } from 'react-router-dom';
-import { Heart, Trash2 } from 'lucide-react';
+import { Heart, ShoppingBag, Trash2 } from 'lucide-react';
 import { useFavorites } from '../../hooks/useFavorites';
 import {
   useCollections,
@@ -8,11 +8,18 @@ import {
   useAllItemTagsBatch,
   useAllItemCollectionsBatch,
 } from '../../hooks/useCollections';
-import { setItemTags } from '../../lib/booth-api';
+import {
+  cachedImageUrl,
+  markOwned,
+  setFavoriteNote,
+  setItemTags,
+  unmarkOwned,
+} from '../../lib/booth-api';
 import { useQueryClient } from '@tanstack/react-query';
 import { useI18n } from '../../lib/i18n';
 import type { FavoriteItem } from '../../lib/types';
 import TagEditor from './TagEditor';
+import NoteEditor from './NoteEditor';
 import AddToCollectionMenu from './AddToCollectionMenu';
 
 interface Props {
@@ -33,11 +40,31 @@ export default memo(function FavoritesList({ items }: Props) {
     async (itemId: number, tags: string[]) => {
       await setItemTags(itemId, tags);
       qc.invalidateQueries({ queryKey: ['all-item-tags-batch'] });
+      qc.invalidateQueries({ queryKey: ['favorites', 'query'] });
       qc.invalidateQueries({ queryKey: ['all-user-tags'] });
     },
     [qc],
   );
 
+  const handleToggleOwned = useCallback(
+    async (fav: FavoriteItem) => {
+      await (fav.owned ? unmarkOwned(fav.item_id) : markOwned(fav.item_id));
+      qc.invalidateQueries({ queryKey: ['favorites'] });
+      qc.invalidateQueries({ queryKey: ['collection-items'] });
+      qc.invalidateQueries({ queryKey: ['all-statistics'] });
+    },
+    [qc],
+  );
+
+  const handleSetNote = useCallback(
+    async (itemId: number, note: string | null) => {
+      await setFavoriteNote(itemId, note);
+      qc.invalidateQueries({ queryKey: ['favorites'] });
+      qc.invalidateQueries({ queryKey: ['collection-items'] });
+    },
+    [qc],
+  );
+
   if (isLoading && !items) {
     return (
       <div className="flex items-center justify-center py-20">
@@ -66,7 +93,7 @@ export default memo(function FavoritesList({ items }: Props) {
             <div className="aspect-square bg-gray-100 relative overflow-hidden">
               {fav.thumbnail_url ? (
                 <img
-                  src={fav.thumbnail_url}
+                  src={cachedImageUrl(fav.thumbnail_url)}
                   alt={fav.name}
                   className="w-full h-full object-cover group-hover:scale-105 transition-transform"
                   loading="lazy"
@@ -76,6 +103,14 @@ export default memo(function FavoritesList({ items }: Props) {
                   No Image
                 </div>
               )}
+              {(fav.availability === 'not_found' || fav.availability === 'private') && (
+                <span
+                  className="absolute top-2 left-2 px-1.5 py-0.5 rounded bg-gray-900/75 text-white text-xs"
+                  title={fav.last_checked_at ?? undefined}
+                >
+                  {t.favorites.unavailable[fav.availability]}
+                </span>
+              )}
             </div>
           </Link>
           <div className="p-3">
@@ -95,6 +130,19 @@ export default memo(function FavoritesList({ items }: Props) {
                   onAddToCollection={addItem}
                   onRemoveFromCollection={removeItem}
                 />
+                <button
+                  onClick={() =>
+                    handleToggleOwned(fav).catch((e) => console.error('Ownership update failed:', e))
+                  }
+                  className={`p-1 transition-colors ${fav.owned ? 'text-indigo-600 hover:text-gray-400' : 'text-gray-300 hover:text-indigo-600'}`}
+                  title={
+                    fav.owned
+                      ? `${t.favorites.owned}${fav.purchased_at ? ` (${fav.purchased_at})` : ''}`
+                      : t.favorites.markOwned
+                  }
+                >
+                  <ShoppingBag className="w-4 h-4" />
+                </button>
                 <button
                   onClick={() =>
                     removeFavorite(fav.item_id).catch((e) => console.error('Remove failed:', e))
@@ -107,11 +155,24 @@ export default memo(function FavoritesList({ items }: Props) {
               </div>
             </div>
             <div className="mt-2 flex items-center justify-between">
-              <span
-                className={`text-sm font-bold ${fav.price === 0 ? 'text-green-600' : 'text-gray-900'}`}
-              >
-                {fav.price === 0 ? t.item.free : `¥${fav.price.toLocaleString()}`}
-              </span>
+              {fav.price_dropped && fav.current_price != null ? (
+                <span className="flex items-baseline gap-1" title={t.favorites.priceDropped}>
+                  <span className="text-sm font-bold text-red-600">
+                    {fav.current_price === 0
+                      ? t.item.free
+                      : `¥${fav.current_price.toLocaleString()}`}
+                  </span>
+                  <span className="text-xs text-gray-400 line-through">
+                    ¥{fav.added_price.toLocaleString()}
+                  </span>
+                </span>
+              ) : (
+                <span
+                  className={`text-sm font-bold ${fav.price === 0 ? 'text-green-600' : 'text-gray-900'}`}
+                >
+                  {fav.price === 0 ? t.item.free : `¥${fav.price.toLocaleString()}`}
+                </span>
+              )}
               {fav.shop_name && (
                 <span className="text-xs text-gray-500 truncate max-w-[120px]">
                   {fav.shop_name}
@@ -126,6 +187,9 @@ export default memo(function FavoritesList({ items }: Props) {
                 onSetTags={handleSetTags}
               />
             </div>
+            <div className="mt-1.5">
+              <NoteEditor itemId={fav.item_id} note={fav.note} onSetNote={handleSetNote} />
+            </div>
           </div>
         </div>
       ))}
@@ -0,0 +1,147 @@
+import { useState } from 'react';
+import { Upload } from 'lucide-react';
+import { useQueryClient } from '@tanstack/react-query';
+import { importItems } from '../../lib/booth-api';
+import { useCollections } from '../../hooks/useCollections';
+import { useI18n } from '../../lib/i18n';
+import { errorMessage, isAppError } from '../../lib/errors';
+import type { ImportProgress, ImportSummary } from '../../lib/types';
+
+interface Props {
+  onClose: () => void;
+}
+
+/** Paste Booth URLs or load a browser bookmarks export to add them as favorites. */
+export default function ImportPanel({ onClose }: Props) {
+  const [source, setSource] = useState('');
+  const [tags, setTags] = useState('');
+  const [collectionId, setCollectionId] = useState<number | null>(null);
+  const [progress, setProgress] = useState<ImportProgress | null>(null);
+  const [summary, setSummary] = useState<ImportSummary | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [running, setRunning] = useState(false);
+  const { collections } = useCollections();
+  const qc = useQueryClient();
+  const { t } = useI18n();
+
+  const loadFile = async (file: File | undefined) => {
+    if (file) setSource(await file.text());
+  };
+
+  const start = async () => {
+    setRunning(true);
+    setSummary(null);
+    setError(null);
+    try {
+      const result = await importItems(
+        source,
+        {
+          tags: tags
+            .split(',')
+            .map((tag) => tag.trim())
+            .filter(Boolean),
+          collection_id: collectionId ?? undefined,
+        },
+        setProgress,
+      );
+      setSummary(result);
+      qc.invalidateQueries({ queryKey: ['favorites'] });
+      qc.invalidateQueries({ queryKey: ['collections'] });
+      qc.invalidateQueries({ queryKey: ['collection-items'] });
+      qc.invalidateQueries({ queryKey: ['all-item-tags-batch'] });
+      qc.invalidateQueries({ queryKey: ['all-item-collections-batch'] });
+      qc.invalidateQueries({ queryKey: ['all-user-tags'] });
+    } catch (e) {
+      console.error('Import failed:', e);
+      // Rejected up front: no item URLs in the input, or more than one import takes
+      setError(
+        isAppError(e) && e.code === 'validation'
+          ? t.favorites.importNothingFound
+          : errorMessage(e, t),
+      );
+    } finally {
+      setRunning(false);
+      setProgress(null);
+    }
+  };
+
+  return (
+    <div className="mb-4 p-4 bg-white border border-gray-200 rounded-lg space-y-3">
+      <textarea
+        value={source}
+        onChange={(e) => setSource(e.target.value)}
+        placeholder={t.favorites.importPlaceholder}
+        rows={5}
+        disabled={running}
+        className="w-full px-3 py-2 text-sm border border-gray-200 rounded-lg resize-y focus:outline-none focus:ring-2 focus:ring-indigo-500"
+      />
+      <div className="flex flex-wrap items-center gap-3">
+        <label className="flex items-center gap-1.5 px-3 py-1.5 text-sm text-gray-600 border border-gray-200 rounded-lg hover:bg-gray-50 cursor-pointer">
+          <Upload className="w-4 h-4" />
+          {t.favorites.importFile}
+          <input
+            type="file"
+            accept=".html,.htm,.txt"
+            className="hidden"
+            disabled={running}
+            onChange={(e) => loadFile(e.target.files?.[0])}
+          />
+        </label>
+        <input
+          value={tags}
+          onChange={(e) => setTags(e.target.value)}
+          placeholder={t.favorites.importTags}
+          disabled={running}
+          className="px-3 py-1.5 text-sm border border-gray-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-indigo-500"
+        />
+        <select
+          value={collectionId ?? ''}
+          onChange={(e) => setCollectionId(e.target.value ? Number(e.target.value) : null)}
+          disabled={running}
+          className="px-3 py-1.5 text-sm border border-gray-200 rounded-lg"
+        >
+          <option value="">{t.collections.addTo}</option>
+          {collections.map((col) => (
+            <option key={col.id} value={col.id}>
+              {col.name}
+            </option>
+          ))}
+        </select>
+        <div className="ml-auto flex items-center gap-2">
+          <button
+            onClick={onClose}
+            disabled={running}
+            className="px-3 py-1.5 text-sm text-gray-500 hover:text-gray-700 disabled:opacity-50"
+          >
+            {t.common.cancel}
+          </button>
+          <button
+            onClick={start}
+            disabled={running || !source.trim()}
+            className="px-3 py-1.5 text-sm text-white bg-indigo-600 rounded-lg hover:bg-indigo-700 disabled:opacity-50"
+          >
+            {progress ? `${progress.completed}/${progress.total}` : t.favorites.importStart}
+          </button>
+        </div>
+      </div>
+      {error && <p className="text-sm text-red-600">{error}</p>}
+      {summary && (
+        <div className="text-sm text-gray-600 space-y-1">
+          <p>
+            {summary.added.length} {t.favorites.importAdded} · {summary.duplicates.length}{' '}
+            {t.favorites.importDuplicates} · {summary.failed.length} {t.favorites.refreshFailed}
+          </p>
+          {summary.failed.length > 0 && (
+            <ul className="text-xs text-gray-400">
+              {summary.failed.map((f) => (
+                <li key={f.item_id}>
+                  #{f.item_id}: {errorMessage(f.error, t)}
+                </li>
+              ))}
+            </ul>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
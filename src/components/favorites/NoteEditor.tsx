@@ -0,0 +1,73 @@
+import { useState, useEffect } from 'react';
+import { StickyNote } from 'lucide-react';
+import { useI18n } from '../../lib/i18n';
+
+/** Must match MAX_NOTE_CHARS in commands/db.rs */
+const MAX_NOTE_CHARS = 2000;
+
+interface Props {
+  itemId: number;
+  note: string | null;
+  onSetNote: (itemId: number, note: string | null) => Promise<void>;
+}
+
+export default function NoteEditor({ itemId, note, onSetNote }: Props) {
+  const [editing, setEditing] = useState(false);
+  const [draft, setDraft] = useState(note ?? '');
+  const { t } = useI18n();
+
+  useEffect(() => {
+    if (!editing) setDraft(note ?? '');
+  }, [note, editing]);
+
+  const save = async () => {
+    setEditing(false);
+    const trimmed = draft.trim();
+    if (trimmed === (note ?? '')) return;
+    try {
+      await onSetNote(itemId, trimmed || null);
+    } catch (e) {
+      console.error('Save note failed:', e);
+      setDraft(note ?? '');
+    }
+  };
+
+  const handleKeyDown = (e: React.KeyboardEvent) => {
+    if (e.key === 'Enter' && !e.shiftKey) {
+      e.preventDefault();
+      save();
+    } else if (e.key === 'Escape') {
+      setDraft(note ?? '');
+      setEditing(false);
+    }
+  };
+
+  if (editing) {
+    return (
+      <textarea
+        autoFocus
+        value={draft}
+        onChange={(e) => setDraft(e.target.value)}
+        onBlur={save}
+        onKeyDown={handleKeyDown}
+        placeholder={t.favorites.notePlaceholder}
+        maxLength={MAX_NOTE_CHARS}
+        rows={3}
+        className="w-full px-1.5 py-1 text-xs border border-gray-200 rounded resize-none focus:outline-none focus:ring-1 focus:ring-indigo-500"
+      />
+    );
+  }
+
+  return (
+    <button
+      onClick={() => setEditing(true)}
+      className="w-full flex items-start gap-1 text-left text-xs text-gray-500 hover:text-gray-700"
+      title={note ?? t.favorites.notePlaceholder}
+    >
+      <StickyNote className="w-3 h-3 mt-0.5 shrink-0" />
+      <span className={`line-clamp-2 ${note ? '' : 'text-gray-300'}`}>
+        {note ?? t.favorites.notePlaceholder}
+      </span>
+    </button>
+  );
+}
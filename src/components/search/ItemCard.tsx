@@ -6,6 +6,7 @@ import { useI18n } from '../../lib/i18n';
 import FavoriteButton from '../favorites/FavoriteButton';
 import { useToast } from '../../lib/ToastContext';
 import { useTranslation } from '../../hooks/useTranslation';
+import { cachedImageUrl } from '../../lib/booth-api';
 
 interface Props {
   item: BoothItem;
@@ -37,7 +38,7 @@ export default memo(function ItemCard({ item, favorited, onAddFavorite, onRemove
         <div className="aspect-square bg-gray-100 relative overflow-hidden">
           {thumbnail ? (
             <img
-              src={thumbnail}
+              src={cachedImageUrl(thumbnail)}
               alt={item.name}
               className="w-full h-full object-cover group-hover:scale-105 transition-transform"
               loading="lazy"
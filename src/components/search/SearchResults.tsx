@@ -1,12 +1,14 @@
 import type { BoothItem } from '../../lib/types';
 import { useI18n } from '../../lib/i18n';
+import { errorMessage } from '../../lib/errors';
 import { useFavorites } from '../../hooks/useFavorites';
 import ItemCard from './ItemCard';
 
 interface Props {
   items: BoothItem[];
   isLoading: boolean;
-  error: string | null;
+  /** Rejection from the backend; localized here by error code */
+  error: unknown;
   totalCount?: number | null;
 }
 
@@ -31,7 +33,7 @@ export default function SearchResults({ items, isLoading, error, totalCount }: P
   if (error) {
     return (
       <div className="flex items-center justify-center py-20" role="alert">
-        <p className="text-sm text-red-500">{error}</p>
+        <p className="text-sm text-red-500">{errorMessage(error, t)}</p>
       </div>
     );
   }
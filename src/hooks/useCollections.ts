@@ -37,6 +37,7 @@ export function useCollections() {
     onSuccess: () => {
       qc.invalidateQueries({ queryKey: ['collections'] });
       qc.invalidateQueries({ queryKey: ['collection-items'] });
+      qc.invalidateQueries({ queryKey: ['favorites', 'query'] });
     },
   });
 
@@ -46,6 +47,7 @@ export function useCollections() {
     onSuccess: () => {
       qc.invalidateQueries({ queryKey: ['collections'] });
       qc.invalidateQueries({ queryKey: ['collection-items'] });
+      qc.invalidateQueries({ queryKey: ['favorites', 'query'] });
       qc.invalidateQueries({ queryKey: ['item-collections'] });
       qc.invalidateQueries({ queryKey: ['all-item-collections-batch'] });
     },
@@ -57,6 +59,7 @@ export function useCollections() {
     onSuccess: () => {
       qc.invalidateQueries({ queryKey: ['collections'] });
       qc.invalidateQueries({ queryKey: ['collection-items'] });
+      qc.invalidateQueries({ queryKey: ['favorites', 'query'] });
       qc.invalidateQueries({ queryKey: ['item-collections'] });
       qc.invalidateQueries({ queryKey: ['all-item-collections-batch'] });
     },
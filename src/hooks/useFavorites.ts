@@ -1,11 +1,18 @@
 import { useMemo } from 'react';
-import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
+import { keepPreviousData, useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
 import {
   getFavorites,
+  queryFavorites,
   addFavorite as addFavoriteApi,
   removeFavorite as removeFavoriteApi,
 } from '../lib/booth-api';
-import type { BoothItem, FavoriteItem } from '../lib/types';
+import type {
+  BoothItem,
+  FavoriteItem,
+  FavoritesFilter,
+  FavoritesPage,
+  FavoritesSort,
+} from '../lib/types';
 
 export function useFavorites() {
   const queryClient = useQueryClient();
@@ -47,6 +54,18 @@ export function useFavorites() {
           shop_name: item.shop_name,
           added_at: new Date().toISOString(),
           note: null,
+          note_updated_at: null,
+          refreshed_at: null,
+          availability: 'unknown',
+          last_checked_at: null,
+          owned: false,
+          purchased_at: null,
+          price_paid: null,
+          purchased_variation: null,
+          order_ref: null,
+          current_price: item.price,
+          added_price: item.price,
+          price_dropped: false,
         },
         ...old,
       ]);
@@ -94,3 +113,19 @@ export function useFavorites() {
     isFavorite,
   };
 }
+
+/**
+ * One server-side filtered page of favorites. Lives under the `favorites` key, so
+ * everything that invalidates favorites refreshes it too.
+ */
+export function useFavoritesQuery(
+  filter: FavoritesFilter,
+  sort: FavoritesSort = 'added_desc',
+  limit?: number,
+) {
+  return useQuery<FavoritesPage>({
+    queryKey: ['favorites', 'query', filter, sort, limit],
+    queryFn: () => queryFavorites(filter, sort, 0, limit),
+    placeholderData: keepPreviousData,
+  });
+}
@@ -0,0 +1,50 @@
+import { useState, useCallback } from 'react';
+import { refreshFavorites, setFavoritesAutoRefresh } from '../lib/booth-api';
+import type { RefreshProgress, RefreshSummary } from '../lib/types';
+
+const AUTO_REFRESH_KEY = 'boothhunter-favorites-auto-refresh';
+/** Favorites not refreshed for this long are re-fetched when auto-refresh is on */
+const AUTO_REFRESH_HOURS = 24;
+
+function storedAutoRefresh(): boolean {
+  return localStorage.getItem(AUTO_REFRESH_KEY) === 'true';
+}
+
+/** Push the saved auto-refresh preference to the backend; call once on startup. */
+export function applyStoredAutoRefresh() {
+  if (storedAutoRefresh()) {
+    setFavoritesAutoRefresh(AUTO_REFRESH_HOURS).catch((e) =>
+      console.error('Failed to enable auto-refresh:', e),
+    );
+  }
+}
+
+export function useFavoritesRefresh() {
+  const [progress, setProgress] = useState<RefreshProgress | null>(null);
+  const [isRefreshing, setIsRefreshing] = useState(false);
+  const [autoRefresh, setAutoRefreshState] = useState(storedAutoRefresh);
+
+  const refresh = useCallback(async (): Promise<RefreshSummary | null> => {
+    setIsRefreshing(true);
+    setProgress(null);
+    try {
+      return await refreshFavorites(setProgress);
+    } catch (e) {
+      console.error('Favorites refresh failed:', e);
+      return null;
+    } finally {
+      setIsRefreshing(false);
+      setProgress(null);
+    }
+  }, []);
+
+  const setAutoRefresh = useCallback((enabled: boolean) => {
+    localStorage.setItem(AUTO_REFRESH_KEY, String(enabled));
+    setAutoRefreshState(enabled);
+    setFavoritesAutoRefresh(enabled ? AUTO_REFRESH_HOURS : null).catch((e) =>
+      console.error('Failed to set auto-refresh:', e),
+    );
+  }, []);
+
+  return { refresh, isRefreshing, progress, autoRefresh, setAutoRefresh };
+}
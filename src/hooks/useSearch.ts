@@ -3,6 +3,7 @@ import { useSearchParams } from 'react-router-dom';
 import { useQuery } from '@tanstack/react-query';
 import { searchBooth, cacheItems, saveSearchHistory, enrichWithWishCount } from '../lib/booth-api';
 import { useSearchContext } from '../lib/SearchContext';
+import { isRetryable } from '../lib/errors';
 import type { SearchParams, BoothItem } from '../lib/types';
 
 // ── URL ↔ SearchParams helpers ──────────────────────
@@ -60,13 +61,18 @@ export function useSearch() {
     queryFn: async () => {
       if (!params) throw new Error('No params');
       const result = await searchBooth(params);
-      cacheItems(result.items).catch((e) => console.error('Failed to cache items:', e));
+      // Re-caching offline results would make stale rows look fresh
+      if (!result.offline) {
+        cacheItems(result.items).catch((e) => console.error('Failed to cache items:', e));
+      }
       saveSearchHistory(params.keyword).catch((e) =>
         console.error('Failed to save search history:', e),
       );
       return result;
     },
     enabled: !!params,
+    // The backend already waits out 429s; retry once more only for transient failures
+    retry: (failureCount, error) => failureCount < 1 && isRetryable(error),
     staleTime: 2 * 60 * 1000,
     gcTime: 5 * 60 * 1000,
   });
@@ -99,9 +105,9 @@ export function useSearch() {
       }
     })
       .then((final) => {
+        // The backend job has already written the counts to the cache
         if (!controller.signal.aborted) {
           setEnrichedItems(final);
-          cacheItems(final).catch(() => {});
         }
       })
       .catch(() => {})
@@ -171,14 +177,11 @@ export function useSearch() {
     updateFilters,
     items: filteredItems,
     totalCount: query.data?.total_count ?? null,
+    isOffline: query.data?.offline ?? false,
     currentPage: params?.page ?? 1,
     isLoading: query.isLoading || query.isFetching,
     isEnriching,
-    error: query.error
-      ? query.error instanceof Error
-        ? query.error.message
-        : String(query.error)
-      : null,
+    error: query.error ?? null,
     hasSearched: !!params,
     currentParams: params,
   };
@@ -1,5 +1,5 @@
-import { useQuery } from '@tanstack/react-query';
-import { getAllStatistics } from '../lib/booth-api';
+import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
+import { clearImageCache, getAllStatistics, getImageCacheUsage } from '../lib/booth-api';
 
 export function useStatistics() {
   const query = useQuery({
@@ -21,3 +21,23 @@ export function useStatistics() {
     isError: query.isError,
   };
 }
+
+export function useImageCache() {
+  const queryClient = useQueryClient();
+
+  const query = useQuery({
+    queryKey: ['image-cache-usage'],
+    queryFn: getImageCacheUsage,
+  });
+
+  const clearMut = useMutation({
+    mutationFn: clearImageCache,
+    onSettled: () => queryClient.invalidateQueries({ queryKey: ['image-cache-usage'] }),
+  });
+
+  return {
+    usage: query.data,
+    clear: () => clearMut.mutate(),
+    isClearing: clearMut.isPending,
+  };
+}
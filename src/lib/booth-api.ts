@@ -1,245 +1,386 @@
-import { fetch } from '@tauri-apps/plugin-http';
-import { invoke } from '@tauri-apps/api/core';
-import { parseSearchHtml, parseItemDetailHtml } from './booth-parser';
+import { convertFileSrc, invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import type {
+  AddFavoriteParams,
+  AllStatistics,
   BoothItem,
+  BoothLocale,
+  BulkItemResult,
+  CachedItem,
+  Collection,
+  CrawlDone,
+  CrawlJob,
+  CrawlPage,
+  ExportFormat,
+  FavoriteChange,
+  FavoriteItem,
+  FavoritesExport,
+  FavoritesFilter,
+  FavoritesPage,
+  FavoritesSort,
+  FullTextHit,
+  FullTextScope,
+  OwnershipParams,
+  PricePoint,
+  RefreshJob,
+  RefreshProgress,
+  RefreshSummary,
+  ImageCacheUsage,
+  ImportJob,
+  ImportOptions,
+  ImportProgress,
+  ImportSummary,
+  RequestPriority,
   SearchParams,
   SearchResult,
-  FavoriteItem,
-  Collection,
-  AllStatistics,
+  ShopItemsResult,
+  WishCountDone,
+  WishCountJob,
+  WishCountProgress,
 } from './types';
 
-// ── Rate limiters (separate queues for different priorities) ──
-
-const RATE_LIMIT_MS = 1000;
-const HEADERS = {
-  'User-Agent':
-    'Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36',
-};
-
-function createRateLimiter(delayMs: number) {
-  let queue: Promise<void> = Promise.resolve();
-  return async function limitedFetch(url: string): Promise<Response> {
-    const ticket = queue.then(() => new Promise<void>((r) => setTimeout(r, delayMs)));
-    queue = ticket;
-    await ticket;
-    return fetch(url, { headers: HEADERS });
-  };
+// ── Search / item detail (Rust backend, shared scheduler) ──
+
+export async function searchBooth(
+  params: SearchParams,
+  priority: RequestPriority = 'user',
+): Promise<SearchResult> {
+  return invoke<SearchResult>('search_booth', { params, priority });
 }
 
-/** Primary limiter for user-initiated searches and item detail fetches */
-const rateLimitedFetch = createRateLimiter(RATE_LIMIT_MS);
+/** Search only the local cache; results are always flagged `offline`. */
+export async function searchCachedItems(params: SearchParams): Promise<SearchResult> {
+  return invoke<SearchResult>('search_cached_items', { params });
+}
 
-/** Separate limiter for wish-count enrichment — runs independently of user searches */
-const enrichFetch = createRateLimiter(RATE_LIMIT_MS);
+export async function getBoothItem(
+  itemId: number,
+  priority: RequestPriority = 'user',
+  locale?: BoothLocale,
+): Promise<BoothItem> {
+  return invoke<BoothItem>('get_booth_item', { itemId, locale, priority });
+}
 
-/** Separate limiter for background avatar updates — never blocks user activity */
-const backgroundFetch = createRateLimiter(1500);
+/** `subdomain` may also be a full shop URL such as `https://foo.booth.pm/items`. */
+export async function getShopItems(
+  subdomain: string,
+  page = 1,
+  priority: RequestPriority = 'user',
+  locale?: BoothLocale,
+): Promise<ShopItemsResult> {
+  return invoke<ShopItemsResult>('get_shop_items', { subdomain, page, locale, priority });
+}
 
-// ── URL builder (ported from Rust client.rs) ─────────
+/**
+ * Item detail from the local cache when possible. Stale rows are returned
+ * immediately and refreshed in the background; subscribe with `onItemUpdated`.
+ */
+export async function getCachedItem(itemId: number, locale?: BoothLocale): Promise<CachedItem> {
+  return invoke<CachedItem>('get_cached_item', { itemId, locale });
+}
 
-function buildSearchUrl(params: SearchParams): string {
-  const page = Math.min(params.page ?? 1, 10_000);
-  const keyword = params.keyword.trim();
-  const keywordEmpty = keyword === '';
+/** Listen for background refreshes started by `getCachedItem`. Returns an unlisten function. */
+export function onItemUpdated(callback: (item: BoothItem) => void): Promise<() => void> {
+  return listen<BoothItem>('item-updated', ({ payload }) => callback(payload));
+}
 
-  let url: string;
-  if (params.category) {
-    if (keywordEmpty) {
-      url = `https://booth.pm/ja/browse/${encodeURIComponent(params.category)}?page=${page}`;
-    } else {
-      url = `https://booth.pm/ja/browse/${encodeURIComponent(params.category)}?q=${encodeURIComponent(keyword)}&page=${page}`;
-    }
-  } else {
-    url = `https://booth.pm/ja/items?q=${encodeURIComponent(keyword)}&page=${page}`;
-  }
+// ── Multi-page crawl (backend job) ───────────────────
 
-  const validSorts = ['new', 'popular', 'price_asc', 'price_desc'];
-  if (params.sort && validSorts.includes(params.sort)) {
-    url += `&sort=${params.sort}`;
-  }
+/**
+ * Walk up to `maxPages` result pages starting at `params.page`.
+ * Each page's new (de-duplicated) items are passed to `onPage` as they arrive.
+ * Aborting `signal` cancels the crawl; the returned summary's `error` then has code `cancelled`.
+ */
+export async function crawlSearch(
+  params: SearchParams,
+  maxPages: number,
+  onPage: (page: CrawlPage) => void,
+  signal?: AbortSignal,
+): Promise<CrawlDone> {
+  let jobId: number | null = null;
+  const earlyPages: CrawlPage[] = [];
+  const earlyDone: CrawlDone[] = [];
+  let resolveDone: (done: CrawlDone) => void = () => {};
+  const done = new Promise<CrawlDone>((resolve) => (resolveDone = resolve));
+
+  // Listen before starting so no event can slip past
+  const unlistenPage = await listen<CrawlPage>('search-crawl-page', ({ payload }) => {
+    if (jobId == null) earlyPages.push(payload);
+    else if (payload.job_id === jobId) onPage(payload);
+  });
+  const unlistenDone = await listen<CrawlDone>('search-crawl-done', ({ payload }) => {
+    if (jobId == null) earlyDone.push(payload);
+    else if (payload.job_id === jobId) resolveDone(payload);
+  });
+  const cancel = () => {
+    if (jobId != null) invoke('cancel_search_crawl', { jobId });
+  };
 
-  if (params.only_free) {
-    url += '&max_price=0';
-  } else {
-    if (params.price_min != null) url += `&min_price=${params.price_min}`;
-    if (params.price_max != null) url += `&max_price=${params.price_max}`;
+  try {
+    const job = await invoke<CrawlJob>('crawl_search', { params, maxPages });
+    jobId = job.job_id;
+    earlyPages.filter((page) => page.job_id === jobId).forEach(onPage);
+    const finished = earlyDone.find((d) => d.job_id === jobId);
+    if (finished) resolveDone(finished);
+
+    if (signal?.aborted) cancel();
+    signal?.addEventListener('abort', cancel);
+    return await done;
+  } finally {
+    signal?.removeEventListener('abort', cancel);
+    unlistenPage();
+    unlistenDone();
   }
-
-  return url;
 }
 
-/** Exposed for background tasks (avatar updates) that should not block user activity */
-export { backgroundFetch };
-
-// ── Search API (direct fetch + JS parse) ─────────────
+// ── Wish count enrichment (backend job) ──────────────
 
-export async function searchBooth(params: SearchParams): Promise<SearchResult> {
-  const url = buildSearchUrl(params);
-  const resp = await rateLimitedFetch(url);
+/**
+ * Fill in wish_lists_count for items that don't have it yet.
+ * The backend serves fresh counts from its cache and fetches the rest in a
+ * background job, reporting each item through `wish-count-progress` events.
+ * Calls `onProgress` whenever counts arrive so the UI can update incrementally.
+ * Starting another enrichment stops this one; it then resolves with the counts so far.
+ */
+export async function enrichWithWishCount(
+  items: BoothItem[],
+  onProgress: (updated: BoothItem[]) => void,
+): Promise<BoothItem[]> {
+  const result = [...items];
+  const itemIds = result.filter((item) => item.wish_lists_count == null).map((item) => item.id);
+  if (itemIds.length === 0) return result;
 
-  if (resp.status === 429) throw new Error('Rate limited by Booth.pm');
-  if (!resp.ok) throw new Error(`Search returned ${resp.status}`);
+  const indexById = new Map(result.map((item, idx) => [item.id, idx]));
+  const apply = (itemId: number, count: number | null) => {
+    const idx = indexById.get(itemId);
+    if (idx != null && count != null) result[idx] = { ...result[idx], wish_lists_count: count };
+  };
 
-  const html = await resp.text();
-  const { items, totalCount } = parseSearchHtml(html);
+  let jobId: number | null = null;
+  const early: WishCountProgress[] = [];
+  const earlyDone: WishCountDone[] = [];
+  let resolveDone: () => void = () => {};
+  const done = new Promise<void>((resolve) => (resolveDone = resolve));
 
-  return {
-    items,
-    total_count: totalCount,
-    current_page: params.page ?? 1,
+  const handle = (progress: WishCountProgress) => {
+    apply(progress.item_id, progress.wish_count);
+    onProgress([...result]);
   };
-}
 
-// ── Item detail (direct fetch) ───────────────────────
+  // Listen before starting so no event can slip past
+  const unlisten = await listen<WishCountProgress>('wish-count-progress', ({ payload }) => {
+    if (jobId == null) early.push(payload);
+    else if (payload.job_id === jobId) handle(payload);
+  });
+  const unlistenDone = await listen<WishCountDone>('wish-count-done', ({ payload }) => {
+    if (jobId == null) earlyDone.push(payload);
+    else if (payload.job_id === jobId) resolveDone();
+  });
 
-export async function getBoothItem(itemId: number): Promise<BoothItem> {
-  // Try JSON API first
   try {
-    const resp = await rateLimitedFetch(`https://booth.pm/ja/items/${itemId}.json`);
-    if (resp.ok) {
-      const data = await resp.json();
-      const item = jsonToBoothItem(data);
-      if (item) return item;
-    }
-  } catch {
-    // fall through to HTML
+    const job = await invoke<WishCountJob>('start_wish_count_job', { itemIds });
+    for (const [id, count] of Object.entries(job.cached)) apply(Number(id), count);
+    onProgress([...result]);
+    if (job.pending === 0) return result;
+
+    jobId = job.job_id;
+    early.filter((progress) => progress.job_id === jobId).forEach(handle);
+    if (earlyDone.some((d) => d.job_id === jobId)) resolveDone();
+    await done;
+    return result;
+  } finally {
+    unlisten();
+    unlistenDone();
   }
+}
 
-  // Fallback to HTML
-  const resp = await rateLimitedFetch(`https://booth.pm/ja/items/${itemId}`);
-  if (resp.status === 429) throw new Error('Rate limited by Booth.pm');
-  if (!resp.ok) throw new Error(`Item ${itemId} not found`);
+// ── Cache / History (unchanged — Rust invoke) ────────
 
-  const html = await resp.text();
-  const item = parseItemDetailHtml(html, itemId);
-  if (!item) throw new Error(`Item ${itemId} not found in HTML`);
-  return item;
+export async function cacheItems(items: BoothItem[]): Promise<void> {
+  return invoke('cache_items', { items });
 }
 
-// ── JSON item parser (mirrors Rust BoothJsonItemDetail) ──
-
-function parsePrice(val: unknown): number {
-  if (typeof val === 'number') return val;
-  if (typeof val === 'string') {
-    const cleaned = val.replace(/\D/g, '');
-    return Number(cleaned) || 0;
-  }
-  return 0;
+export async function saveSearchHistory(keyword: string): Promise<void> {
+  return invoke('save_search_history', { keyword });
 }
 
-function jsonToBoothItem(data: Record<string, unknown>): BoothItem | null {
-  const id = data.id as number | undefined;
-  if (id == null) return null;
+// ── Favorites (unchanged — Rust invoke) ──────────────
 
-  const images: string[] = [];
-  if (Array.isArray(data.images)) {
-    for (const img of data.images) {
-      const src =
-        (img as Record<string, unknown>).original ?? (img as Record<string, unknown>).resized;
-      if (typeof src === 'string') images.push(src);
-    }
-  }
+export async function getFavorites(filter?: FavoritesFilter): Promise<FavoriteItem[]> {
+  return invoke<FavoriteItem[]>('get_favorites', { filter });
+}
 
-  const tags: string[] = [];
-  if (Array.isArray(data.tags)) {
-    for (const t of data.tags) {
-      const name = (t as Record<string, unknown>).name;
-      if (typeof name === 'string') tags.push(name);
-    }
-  }
+/** One filtered, sorted page of favorites with their tags and collections. */
+export async function queryFavorites(
+  filter: FavoritesFilter,
+  sort: FavoritesSort = 'added_desc',
+  offset = 0,
+  limit?: number,
+): Promise<FavoritesPage> {
+  return invoke<FavoritesPage>('query_favorites', { filter, sort, offset, limit: limit ?? null });
+}
 
-  const category = data.category as Record<string, unknown> | undefined;
-  const shop = data.shop as Record<string, unknown> | undefined;
-
-  const wishListsCount =
-    typeof data.wish_lists_count === 'number' ? data.wish_lists_count : undefined;
-
-  return {
-    id,
-    name: (data.name as string) || '',
-    description: (data.description as string) || null,
-    price: parsePrice(data.price),
-    category_name: (category?.name as string) || null,
-    shop_name: (shop?.name as string) || null,
-    url: (data.url as string) || `https://booth.pm/ja/items/${id}`,
-    images,
-    tags,
-    wish_lists_count: wishListsCount,
-  };
+export async function addFavorite(params: AddFavoriteParams): Promise<void> {
+  return invoke('add_favorite', { params });
+}
+
+export async function removeFavorite(itemId: number): Promise<void> {
+  return invoke('remove_favorite', { itemId });
 }
 
-// ── Wish count enrichment ────────────────────────────
+/** Observed prices of a favorite, oldest first. */
+export async function getPriceHistory(itemId: number): Promise<PricePoint[]> {
+  return invoke<PricePoint[]>('get_price_history', { itemId });
+}
 
-const ENRICH_CONCURRENCY = 3;
+/** Set the personal note on a favorite; an empty note clears it. */
+export async function setFavoriteNote(itemId: number, note: string | null): Promise<void> {
+  return invoke('set_favorite_note', { itemId, note });
+}
 
 /**
- * Fetch wish_lists_count for items that don't have it yet.
- * Calls the JSON API for each item with concurrency limiting.
- * Returns a new array with wish counts filled in progressively.
- * Calls `onProgress` after each batch so the UI can update incrementally.
+ * Ranked trigram search over favorites (name, shop, note, tags) and cached items.
+ * Queries of only 1-2 character terms scan instead of using the index, newest first.
  */
-export async function enrichWithWishCount(
-  items: BoothItem[],
-  onProgress: (updated: BoothItem[]) => void,
-): Promise<BoothItem[]> {
-  const result = [...items];
-  const toFetch = result
-    .map((item, idx) => ({ item, idx }))
-    .filter(({ item }) => item.wish_lists_count == null);
-
-  for (let i = 0; i < toFetch.length; i += ENRICH_CONCURRENCY) {
-    const batch = toFetch.slice(i, i + ENRICH_CONCURRENCY);
-    await Promise.allSettled(
-      batch.map(async ({ item, idx }) => {
-        try {
-          const resp = await enrichFetch(`https://booth.pm/ja/items/${item.id}.json`);
-          if (resp.ok) {
-            const data = await resp.json();
-            const count = typeof data.wish_lists_count === 'number' ? data.wish_lists_count : 0;
-            result[idx] = { ...result[idx], wish_lists_count: count };
-          }
-        } catch {
-          // Skip failed items — leave wish_lists_count as undefined
-        }
-      }),
-    );
-    onProgress([...result]);
-  }
+export async function fullTextSearch(
+  query: string,
+  scope: FullTextScope = 'all',
+  limit?: number,
+): Promise<FullTextHit[]> {
+  return invoke<FullTextHit[]>('full_text_search', { query, scope, limit: limit ?? null });
+}
 
-  return result;
+/** Mark a favorite as bought; calling it again replaces the purchase details. */
+export async function markOwned(itemId: number, params?: OwnershipParams): Promise<void> {
+  return invoke('mark_owned', { itemId, params: params ?? null });
 }
 
-// ── Cache / History (unchanged — Rust invoke) ────────
+/** Move a favorite back to the wishlist, dropping its purchase details. */
+export async function unmarkOwned(itemId: number): Promise<void> {
+  return invoke('unmark_owned', { itemId });
+}
 
-export async function cacheItems(items: BoothItem[]): Promise<void> {
-  return invoke('cache_items', { items });
+// ── Image cache ──────────────────────────────────────
+
+/** Route a Booth image through the local disk cache (`boothimg://` scheme). */
+export function cachedImageUrl(url: string): string {
+  return convertFileSrc(url, 'boothimg');
 }
 
-export async function saveSearchHistory(keyword: string): Promise<void> {
-  return invoke('save_search_history', { keyword });
+export async function getImageCacheUsage(): Promise<ImageCacheUsage> {
+  return invoke<ImageCacheUsage>('get_image_cache_usage');
 }
 
-// ── Favorites (unchanged — Rust invoke) ──────────────
+export async function clearImageCache(): Promise<void> {
+  return invoke('clear_image_cache');
+}
+
+// ── Favorites refresh (backend job) ──────────────────
 
-export async function getFavorites(): Promise<FavoriteItem[]> {
-  return invoke<FavoriteItem[]>('get_favorites');
+/**
+ * Re-fetch favorites (all, or just `itemIds`) and update their stored fields.
+ * `onProgress` fires after each item; resolves with the summary when done.
+ */
+export async function refreshFavorites(
+  onProgress: (progress: RefreshProgress) => void,
+  itemIds?: number[],
+): Promise<RefreshSummary> {
+  let jobId: number | null = null;
+  const earlyProgress: RefreshProgress[] = [];
+  const earlyDone: RefreshSummary[] = [];
+  let resolveDone: (summary: RefreshSummary) => void = () => {};
+  const done = new Promise<RefreshSummary>((resolve) => (resolveDone = resolve));
+
+  // Listen before starting so no event can slip past
+  const unlistenProgress = await listen<RefreshProgress>(
+    'favorites-refresh-progress',
+    ({ payload }) => {
+      if (jobId == null) earlyProgress.push(payload);
+      else if (payload.job_id === jobId) onProgress(payload);
+    },
+  );
+  const unlistenDone = await onFavoritesRefreshed((summary) => {
+    if (jobId == null) earlyDone.push(summary);
+    else if (summary.job_id === jobId) resolveDone(summary);
+  });
+
+  try {
+    const job = await invoke<RefreshJob>('refresh_favorites', { itemIds });
+    jobId = job.job_id;
+    earlyProgress.filter((p) => p.job_id === jobId).forEach(onProgress);
+    const finished = earlyDone.find((d) => d.job_id === jobId);
+    if (finished) resolveDone(finished);
+    return await done;
+  } finally {
+    unlistenProgress();
+    unlistenDone();
+  }
 }
 
-export async function addFavorite(params: {
-  item_id: number;
-  name: string;
-  price: number;
-  thumbnail_url: string | null;
-  category_name: string | null;
-  shop_name: string | null;
-}): Promise<void> {
-  return invoke('add_favorite', { params });
+/** Fires for every finished refresh, including periodic ones. Returns an unlisten function. */
+export function onFavoritesRefreshed(
+  callback: (summary: RefreshSummary) => void,
+): Promise<() => void> {
+  return listen<RefreshSummary>('favorites-refresh-done', ({ payload }) => callback(payload));
 }
 
-export async function removeFavorite(itemId: number): Promise<void> {
-  return invoke('remove_favorite', { itemId });
+/** Refresh favorites older than `intervalHours` in the background; `null` turns it off. */
+export async function setFavoritesAutoRefresh(intervalHours: number | null): Promise<void> {
+  return invoke('set_favorites_auto_refresh', { intervalHours });
+}
+
+export async function getFavoriteChanges(itemId: number): Promise<FavoriteChange[]> {
+  return invoke<FavoriteChange[]>('get_favorite_changes', { itemId });
+}
+
+// ── Import (backend job) ─────────────────────────────
+
+/**
+ * Import Booth item URLs from pasted text or a bookmarks HTML export as favorites.
+ * Resolves with the report once every item was fetched or skipped.
+ */
+export async function importItems(
+  source: string,
+  options: ImportOptions,
+  onProgress: (progress: ImportProgress) => void,
+): Promise<ImportSummary> {
+  let jobId: number | null = null;
+  const earlyProgress: ImportProgress[] = [];
+  const earlyDone: ImportSummary[] = [];
+  let resolveDone: (summary: ImportSummary) => void = () => {};
+  const done = new Promise<ImportSummary>((resolve) => (resolveDone = resolve));
+
+  // Listen before starting so no event can slip past
+  const unlistenProgress = await listen<ImportProgress>('import-items-progress', ({ payload }) => {
+    if (jobId == null) earlyProgress.push(payload);
+    else if (payload.job_id === jobId) onProgress(payload);
+  });
+  const unlistenDone = await listen<ImportSummary>('import-items-done', ({ payload }) => {
+    if (jobId == null) earlyDone.push(payload);
+    else if (payload.job_id === jobId) resolveDone(payload);
+  });
+
+  try {
+    const job = await invoke<ImportJob>('import_items', { source, options });
+    jobId = job.job_id;
+    earlyProgress.filter((p) => p.job_id === jobId).forEach(onProgress);
+    const finished = earlyDone.find((d) => d.job_id === jobId);
+    if (finished) resolveDone(finished);
+    return await done;
+  } finally {
+    unlistenProgress();
+    unlistenDone();
+  }
+}
+
+// ── Export ───────────────────────────────────────────
+
+/** Favorites matching `filter` as a CSV, JSON or Markdown document. */
+export async function exportFavorites(
+  format: ExportFormat,
+  filter?: FavoritesFilter,
+): Promise<FavoritesExport> {
+  return invoke<FavoritesExport>('export_favorites', { format, filter });
 }
 
 // ── Popular Avatars (unchanged — Rust invoke) ────────
@@ -296,8 +437,11 @@ export async function removeFromCollection(collectionId: number, itemId: number)
   return invoke('remove_from_collection', { collectionId, itemId });
 }
 
-export async function getCollectionItems(collectionId: number): Promise<FavoriteItem[]> {
-  return invoke<FavoriteItem[]>('get_collection_items', { collectionId });
+export async function getCollectionItems(
+  collectionId: number,
+  filter?: FavoritesFilter,
+): Promise<FavoriteItem[]> {
+  return invoke<FavoriteItem[]>('get_collection_items', { collectionId, filter });
 }
 
 // ── Item Tags ────────────────────────────────────────
@@ -318,6 +462,39 @@ export async function getAllItemCollectionsBatch(): Promise<Record<number, numbe
   return invoke<Record<number, number[]>>('get_all_item_collections_batch');
 }
 
+// ── Bulk (one transaction, per-item results) ─────────
+
+export async function addFavoritesBulk(items: AddFavoriteParams[]): Promise<BulkItemResult[]> {
+  return invoke<BulkItemResult[]>('add_favorites_bulk', { items });
+}
+
+export async function removeFavoritesBulk(itemIds: number[]): Promise<BulkItemResult[]> {
+  return invoke<BulkItemResult[]>('remove_favorites_bulk', { itemIds });
+}
+
+/** Add tags to every item, keeping their other tags. */
+export async function addTagsBulk(itemIds: number[], tags: string[]): Promise<BulkItemResult[]> {
+  return invoke<BulkItemResult[]>('add_tags_bulk', { itemIds, tags });
+}
+
+/** Remove tags from every item, keeping their other tags. */
+export async function removeTagsBulk(itemIds: number[], tags: string[]): Promise<BulkItemResult[]> {
+  return invoke<BulkItemResult[]>('remove_tags_bulk', { itemIds, tags });
+}
+
+/** Put items into a collection, taking them out of `fromCollectionId` when given. */
+export async function moveItemsToCollection(
+  itemIds: number[],
+  collectionId: number,
+  fromCollectionId?: number,
+): Promise<BulkItemResult[]> {
+  return invoke<BulkItemResult[]>('move_items_to_collection', {
+    itemIds,
+    collectionId,
+    fromCollectionId: fromCollectionId ?? null,
+  });
+}
+
 // ── Statistics ────────────────────────────────────────
 
 export async function getAllStatistics(): Promise<AllStatistics> {
@@ -0,0 +1,28 @@
+import { isAppError, errorMessage, isRetryable } from './errors';
+import { en } from './i18n/en';
+
+const rateLimited = {
+  code: 'rate_limited',
+  message: 'Rate limited by Booth.pm',
+  retryable: true,
+  details: { retry_after_secs: 30 },
+};
+
+describe('errors', () => {
+  it('recognizes backend error payloads', () => {
+    expect(isAppError(rateLimited)).toBe(true);
+    expect(isAppError('Rate limited by Booth.pm')).toBe(false);
+    expect(isAppError({ code: 'teapot', message: 'x' })).toBe(false);
+  });
+
+  it('localizes by code instead of message text', () => {
+    expect(errorMessage(rateLimited, en)).toBe(en.errors.rate_limited);
+    expect(errorMessage(new Error('boom'), en)).toBe(en.errors.unknown);
+  });
+
+  it('only retries retryable backend errors', () => {
+    expect(isRetryable(rateLimited)).toBe(true);
+    expect(isRetryable({ ...rateLimited, code: 'validation', retryable: false })).toBe(false);
+    expect(isRetryable(new Error('network'))).toBe(false);
+  });
+});
@@ -0,0 +1,36 @@
+import type { AppError, AppErrorCode } from './types';
+import type { Translations } from './i18n';
+
+const CODES: readonly AppErrorCode[] = [
+  'parse_error',
+  'not_found',
+  'private',
+  'database',
+  'io',
+  'network',
+  'http_status',
+  'rate_limited',
+  'validation',
+  'cancelled',
+  'busy',
+];
+
+export function isAppError(e: unknown): e is AppError {
+  return (
+    typeof e === 'object' &&
+    e !== null &&
+    CODES.includes((e as AppError).code) &&
+    typeof (e as AppError).message === 'string'
+  );
+}
+
+/** Localized message for a rejected invoke (or any other thrown value). */
+export function errorMessage(e: unknown, t: Translations): string {
+  if (isAppError(e)) return t.errors[e.code];
+  return t.errors.unknown;
+}
+
+/** Whether a failed call is worth retrying; non-backend errors never are. */
+export function isRetryable(e: unknown): boolean {
+  return isAppError(e) && e.retryable;
+}
@@ -0,0 +1,17 @@
+import { splitHighlights } from './highlights';
+
+describe('splitHighlights', () => {
+  it('splits plain and highlighted runs', () => {
+    expect(splitHighlights('…ふわ\u0002もこパー\u0003カー')).toEqual([
+      { text: '…ふわ', highlight: false },
+      { text: 'もこパー', highlight: true },
+      { text: 'カー', highlight: false },
+    ]);
+  });
+
+  it('keeps text without markers and drops unmatched ones', () => {
+    expect(splitHighlights('Boots')).toEqual([{ text: 'Boots', highlight: false }]);
+    expect(splitHighlights('a\u0002b')).toEqual([{ text: 'ab', highlight: false }]);
+    expect(splitHighlights('')).toEqual([]);
+  });
+});
@@ -0,0 +1,26 @@
+/** Backend snippet markers (`full_text::HIGHLIGHT_START` / `HIGHLIGHT_END`) */
+const START = '\u0002';
+const END = '\u0003';
+
+export interface HighlightRun {
+  text: string;
+  highlight: boolean;
+}
+
+/** Split a full-text snippet into plain and highlighted runs. */
+export function splitHighlights(snippet: string): HighlightRun[] {
+  const runs: HighlightRun[] = [];
+  let rest = snippet;
+  while (rest) {
+    const start = rest.indexOf(START);
+    const end = start === -1 ? -1 : rest.indexOf(END, start);
+    if (end === -1) {
+      runs.push({ text: rest.replaceAll(START, ''), highlight: false });
+      break;
+    }
+    if (start > 0) runs.push({ text: rest.slice(0, start), highlight: false });
+    runs.push({ text: rest.slice(start + 1, end), highlight: true });
+    rest = rest.slice(end + 1);
+  }
+  return runs;
+}
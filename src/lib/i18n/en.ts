@@ -9,12 +9,34 @@ export const en: Translations = {
     searching: 'Searching...',
     error: 'An error occurred while searching',
     rateLimited: 'Too many requests. Please try again later.',
+    offline: "Booth can't be reached. Showing saved results, which may be out of date.",
   },
   favorites: {
     title: 'Favorites',
     empty: 'No favorite items yet',
     added: 'Added to favorites',
     removed: 'Removed from favorites',
+    notePlaceholder: 'Add a note...',
+    priceDropped: 'Price dropped',
+    refresh: 'Refresh from Booth',
+    autoRefresh: 'Auto-refresh daily',
+    refreshUpdated: 'updated',
+    refreshUnchanged: 'unchanged',
+    unavailable: { not_found: 'Removed from Booth', private: 'Private on Booth' },
+    owned: 'Owned',
+    markOwned: 'Mark as owned',
+    showMore: 'Show more',
+    importItems: 'Import',
+    importPlaceholder: 'Paste Booth item URLs, one or many per line',
+    importFile: 'Bookmarks file',
+    importTags: 'Tags (comma separated)',
+    importStart: 'Import',
+    importAdded: 'added',
+    importDuplicates: 'already in favorites',
+    importNothingFound: 'No Booth item URLs found',
+    export: 'Export';
+    exported: 'exported';
+    refreshFailed: 'failed',
   },
   item: {
     free: 'Free',
@@ -63,6 +85,7 @@ export const en: Translations = {
     prev: 'Previous',
     next: 'Next',
     linkCopied: 'Link copied',
+    cancel: 'Cancel',
     copyLink: 'Copy link',
   },
   translation: {
@@ -76,6 +99,7 @@ export const en: Translations = {
     tags: 'Tags',
     totalSearches: 'Total Searches',
     totalValue: 'Total Value',
+    totalSpent: 'Spent',
     avgPrice: 'Avg. Price',
     categoryDistribution: 'Favorites by Category',
     priceDistribution: 'Price Distribution',
@@ -84,6 +108,9 @@ export const en: Translations = {
     searchHistory: 'Search Keywords',
     monthlyFavorites: 'Monthly Favorites',
     noData: 'No data',
+    imageCache: 'Image Cache',
+    imageCacheFiles: 'images',
+    clearImageCache: 'Clear',
   },
   collections: {
     title: 'Collections',
@@ -132,6 +159,20 @@ export const en: Translations = {
     dismiss: 'Later',
     error: 'An error occurred while updating',
   },
+  errors: {
+    parse_error: 'Could not read the response from Booth',
+    not_found: 'Not found',
+    private: 'This item is private',
+    database: 'A local database error occurred',
+    io: 'Could not read or write a local file',
+    network: 'Could not connect to Booth. Check your internet connection.',
+    http_status: 'Booth returned an unexpected response',
+    rate_limited: 'Too many requests. Please try again later.',
+    validation: 'Invalid input',
+    cancelled: 'Cancelled',
+    busy: 'Already in progress. Please wait for it to finish.',
+    unknown: 'An error occurred',
+  },
   priceBuckets: {
     free: 'Free',
     '~500': '~¥500',
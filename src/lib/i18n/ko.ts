@@ -9,12 +9,34 @@ export const ko: Translations = {
     searching: '검색 중...',
     error: '검색 중 오류가 발생했습니다',
     rateLimited: '요청이 너무 많습니다. 잠시 후 다시 시도해주세요.',
+    offline: 'Booth에 연결할 수 없어 저장된 결과를 표시합니다. 최신 정보가 아닐 수 있습니다.',
   },
   favorites: {
     title: '즐겨찾기',
     empty: '즐겨찾기한 상품이 없습니다',
     added: '즐겨찾기에 추가했습니다',
     removed: '즐겨찾기에서 제거했습니다',
+    notePlaceholder: '메모 추가...',
+    priceDropped: '가격 인하',
+    refresh: 'Booth에서 새로고침',
+    autoRefresh: '매일 자동 새로고침',
+    refreshUpdated: '변경',
+    refreshUnchanged: '변경 없음',
+    unavailable: { not_found: 'Booth에서 삭제됨', private: 'Booth에서 비공개' },
+    owned: '구매함',
+    markOwned: '구매함으로 표시',
+    showMore: '더 보기',
+    importItems: '가져오기',
+    importPlaceholder: 'Booth 상품 URL을 붙여넣으세요 (여러 개 가능)',
+    importFile: '북마크 파일',
+    importTags: '태그 (쉼표로 구분)',
+    importStart: '가져오기',
+    importAdded: '추가',
+    importDuplicates: '이미 즐겨찾기에 있음',
+    importNothingFound: 'Booth 상품 URL을 찾을 수 없습니다',
+    export: '내보내기';
+    exported: '내보냄';
+    refreshFailed: '실패',
   },
   item: {
     free: '무료',
@@ -63,6 +85,7 @@ export const ko: Translations = {
     prev: '이전',
     next: '다음',
     linkCopied: '링크를 복사했습니다',
+    cancel: '취소',
     copyLink: '링크 복사',
   },
   translation: {
@@ -76,6 +99,7 @@ export const ko: Translations = {
     tags: '태그',
     totalSearches: '총 검색',
     totalValue: '총 가치',
+    totalSpent: '구매',
     avgPrice: '평균가',
     categoryDistribution: '카테고리별 즐겨찾기',
     priceDistribution: '가격대별 분포',
@@ -84,6 +108,9 @@ export const ko: Translations = {
     searchHistory: '검색 키워드',
     monthlyFavorites: '월별 즐겨찾기',
     noData: '데이터 없음',
+    imageCache: '이미지 캐시',
+    imageCacheFiles: '개 이미지',
+    clearImageCache: '비우기',
   },
   collections: {
     title: '컬렉션',
@@ -132,6 +159,20 @@ export const ko: Translations = {
     dismiss: '나중에',
     error: '업데이트 중 오류가 발생했습니다',
   },
+  errors: {
+    parse_error: 'Booth 응답을 읽을 수 없습니다',
+    not_found: '찾을 수 없습니다',
+    private: '비공개 상품입니다',
+    database: '로컬 데이터베이스 오류가 발생했습니다',
+    io: '로컬 파일을 읽거나 쓸 수 없습니다',
+    network: 'Booth에 연결할 수 없습니다. 인터넷 연결을 확인해주세요.',
+    http_status: 'Booth에서 예상치 못한 응답을 받았습니다',
+    rate_limited: '요청이 너무 많습니다. 잠시 후 다시 시도해주세요.',
+    validation: '입력값이 올바르지 않습니다',
+    cancelled: '취소되었습니다',
+    busy: '이미 진행 중입니다. 끝날 때까지 기다려주세요.',
+    unknown: '오류가 발생했습니다',
+  },
   priceBuckets: {
     free: '무료',
     '~500': '~500',
@@ -9,12 +9,34 @@ export interface Translations {
     searching: string;
     error: string;
     rateLimited: string;
+    offline: string;
   };
   favorites: {
     title: string;
     empty: string;
     added: string;
     removed: string;
+    notePlaceholder: string;
+    priceDropped: string;
+    refresh: string;
+    autoRefresh: string;
+    refreshUpdated: string;
+    refreshUnchanged: string;
+    unavailable: { not_found: string; private: string };
+    owned: string;
+    markOwned: string;
+    showMore: string;
+    importItems: string;
+    importPlaceholder: string;
+    importFile: string;
+    importTags: string;
+    importStart: string;
+    importAdded: string;
+    importDuplicates: string;
+    importNothingFound: string;
+    export: string;
+    exported: string;
+    refreshFailed: string;
   };
   item: {
     free: string;
@@ -63,6 +85,7 @@ export interface Translations {
     prev: string;
     next: string;
     linkCopied: string;
+    cancel: string;
     copyLink: string;
   };
   translation: {
@@ -76,6 +99,7 @@ export interface Translations {
     tags: string;
     totalSearches: string;
     totalValue: string;
+    totalSpent: string;
     avgPrice: string;
     categoryDistribution: string;
     priceDistribution: string;
@@ -84,6 +108,9 @@ export interface Translations {
     searchHistory: string;
     monthlyFavorites: string;
     noData: string;
+    imageCache: string;
+    imageCacheFiles: string;
+    clearImageCache: string;
   };
   collections: {
     title: string;
@@ -132,6 +159,20 @@ export interface Translations {
     dismiss: string;
     error: string;
   };
+  errors: {
+    parse_error: string;
+    not_found: string;
+    private: string;
+    database: string;
+    io: string;
+    network: string;
+    http_status: string;
+    rate_limited: string;
+    validation: string;
+    cancelled: string;
+    busy: string;
+    unknown: string;
+  };
   priceBuckets: {
     free: string;
     '~500': string;
@@ -1,11 +1,10 @@
 import {
-  backgroundFetch,
+  searchBooth,
   getPopularAvatars as getPopularAvatarsApi,
   checkAvatarsNeedUpdate,
   updatePopularAvatar,
   type PopularAvatar,
 } from './booth-api';
-import { parseSearchHtml } from './booth-parser';
 
 export type { PopularAvatar };
 
@@ -23,12 +22,13 @@ export async function updateAvatarData(): Promise<void> {
   for (const avatar of avatars) {
     try {
       const keyword = `${avatar.name_ja} 対応`;
-      const url = `https://booth.pm/ja/browse/${encodeURIComponent('3D衣装')}?q=${encodeURIComponent(keyword)}&page=1`;
-      const resp = await backgroundFetch(url);
-      if (!resp.ok) continue;
-
-      const html = await resp.text();
-      const { items, totalCount } = parseSearchHtml(html);
+      const {
+        items,
+        total_count: totalCount,
+        offline,
+      } = await searchBooth({ keyword, category: '3D衣装', page: 1 }, 'background');
+      // Cached rows would overwrite Booth's counts with local ones
+      if (offline) continue;
 
       const thumbnailUrl = items[0]?.images[0] ?? avatar.thumbnail_url;
 
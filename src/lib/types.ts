@@ -9,6 +9,29 @@ export interface BoothItem {
   images: string[];
   tags: string[];
   wish_lists_count?: number;
+  /** Only present for items fetched through the detail API */
+  variations?: Variation[];
+  /** Booth site locale the item was fetched from */
+  locale?: BoothLocale;
+}
+
+/** Result of `get_cached_item` */
+export interface CachedItem {
+  item: BoothItem;
+  /** null when nothing was cached and the item was just fetched */
+  cached_at: string | null;
+  /** A background refresh is running; the new item arrives as `item-updated` */
+  stale: boolean;
+}
+
+/** First path segment of `booth.pm/{locale}/...`; defaults to 'ja' in the backend */
+export type BoothLocale = 'ja' | 'en' | 'ko';
+
+export interface Variation {
+  name: string | null;
+  price: number;
+  kind: 'digital' | 'physical';
+  stock: 'available' | 'low' | 'sold_out';
 }
 
 export interface SearchParams {
@@ -19,16 +42,122 @@ export interface SearchParams {
   only_free?: boolean;
   price_min?: number;
   price_max?: number;
+  /** Items must carry every one of these tags */
+  tags?: string[];
+  item_type?: 'digital' | 'physical';
+  in_stock?: boolean;
+  /** Restrict to Booth's "new arrivals" (recently listed items) */
+  new_arrival?: boolean;
+  /** Booth hides adult items unless asked otherwise */
+  adult?: 'exclude' | 'include' | 'only';
+  locale?: BoothLocale;
   /** Client-side only: filter items with at least N wish list adds */
   min_wish_count?: number;
 }
 
+/** Scheduling class for backend Booth requests: user > enrich > background */
+export type RequestPriority = 'user' | 'enrich' | 'background';
+
+/** Every backend command rejects with this shape */
+export type AppErrorCode =
+  | 'parse_error'
+  | 'not_found'
+  | 'private'
+  | 'database'
+  | 'io'
+  | 'network'
+  | 'http_status'
+  | 'rate_limited'
+  | 'validation'
+  | 'cancelled'
+  | 'busy';
+
+export interface AppError {
+  code: AppErrorCode;
+  /** English, for logs; show `t.errors[code]` to users instead */
+  message: string;
+  retryable: boolean;
+  /** http_status: { status, url }; rate_limited: { retry_after_secs } */
+  details: Record<string, unknown> | null;
+}
+
 export interface SearchResult {
   items: BoothItem[];
   total_count: number | null;
   current_page: number;
+  /** Served from the local cache because Booth could not be reached */
+  offline: boolean;
+  /** For offline results: when the oldest returned item was cached */
+  cached_at: string | null;
+}
+
+export interface ShopInfo {
+  subdomain: string;
+  name: string;
+  icon_url: string | null;
+  description: string | null;
+  item_count: number | null;
+}
+
+export interface ShopItemsResult {
+  shop: ShopInfo;
+  items: BoothItem[];
+  current_page: number;
+}
+
+export interface CrawlJob {
+  job_id: number;
+  /** Requested page count after the backend cap */
+  max_pages: number;
+}
+
+/** Payload of `search-crawl-page` */
+export interface CrawlPage {
+  job_id: number;
+  page: number;
+  /** Only items not seen on an earlier page of this crawl */
+  items: BoothItem[];
+  pages_fetched: number;
+  total_items: number;
+  total_count: number | null;
+}
+
+/** Payload of `search-crawl-done` */
+export interface CrawlDone {
+  job_id: number;
+  pages_fetched: number;
+  total_items: number;
+  /** Set when the crawl stopped early; cancellation has code 'cancelled' */
+  error: AppError | null;
+}
+
+export interface WishCountJob {
+  job_id: number;
+  /** Fresh counts already in the backend cache, keyed by item id */
+  cached: Record<number, number>;
+  pending: number;
+}
+
+export interface WishCountProgress {
+  job_id: number;
+  item_id: number;
+  /** null when the fetch failed */
+  wish_count: number | null;
+  completed: number;
+  total: number;
+}
+
+export interface WishCountDone {
+  job_id: number;
+  completed: number;
+  total: number;
+  /** 'cancelled' when a newer job (a newer search) replaced this one */
+  error: AppError | null;
 }
 
+/** Whether Booth still serves a favorite; `unknown` until it is first fetched */
+export type Availability = 'available' | 'not_found' | 'private' | 'unknown';
+
 export interface FavoriteItem {
   id: number;
   item_id: number;
@@ -39,6 +168,185 @@ export interface FavoriteItem {
   shop_name: string | null;
   added_at: string;
   note: string | null;
+  note_updated_at: string | null;
+  /** Last time the stored fields were re-fetched from Booth */
+  refreshed_at: string | null;
+  availability: Availability;
+  /** Last time Booth was asked for the item, whatever the answer */
+  last_checked_at: string | null;
+  /** Bought, as opposed to only wishlisted; the purchase fields are null otherwise */
+  owned: boolean;
+  /** YYYY-MM-DD */
+  purchased_at: string | null;
+  price_paid: number | null;
+  purchased_variation: string | null;
+  order_ref: string | null;
+  /** Latest price seen since the item was favorited */
+  current_price: number | null;
+  /** Price when the item was favorited */
+  added_price: number;
+  /** current_price is below the price when favorited */
+  price_dropped: boolean;
+}
+
+export interface RefreshJob {
+  job_id: number;
+  total: number;
+}
+
+export interface RefreshProgress {
+  job_id: number;
+  item_id: number;
+  completed: number;
+  total: number;
+}
+
+export interface RefreshSummary {
+  job_id: number;
+  total: number;
+  updated: number;
+  unchanged: number;
+  failed: number;
+}
+
+/** A stored favorite field that a refresh found changed on Booth */
+export interface FavoriteChange {
+  item_id: number;
+  field: 'name' | 'price' | 'thumbnail_url' | 'category_name' | 'shop_name';
+  old_value: string | null;
+  new_value: string | null;
+  changed_at: string;
+}
+
+/** One price change of a favorite; points are only recorded when the price changes. */
+export interface PricePoint {
+  price: number;
+  observed_at: string;
+}
+
+/** Server-side narrowing for favorites queries; unset fields match everything. */
+export interface FavoritesFilter {
+  /** Terms that must each appear in the name, shop, category, note or a user tag */
+  text?: string;
+  /** Whitespace-separated terms that must all appear in the note */
+  note?: string;
+  /** User tags, matched exactly */
+  tags?: string[];
+  /** Default 'any' */
+  tag_match?: 'any' | 'all';
+  collection_id?: number;
+  price_min?: number;
+  price_max?: number;
+  shop?: string;
+  category?: string;
+  /** YYYY-MM-DD, inclusive */
+  added_from?: string;
+  /** YYYY-MM-DD, inclusive */
+  added_to?: string;
+  availability?: Availability;
+  /** true: owned items only; false: the wishlist only */
+  owned?: boolean;
+}
+
+export type FavoritesSort = 'added_desc' | 'added_asc' | 'price_asc' | 'price_desc' | 'name';
+
+/** A favorite with its user tags and collection ids */
+export interface FavoriteEntry extends FavoriteItem {
+  tags: string[];
+  collection_ids: number[];
+}
+
+export interface FavoritesPage {
+  items: FavoriteEntry[];
+  /** Matches across all pages */
+  total_count: number;
+  offset: number;
+  limit: number;
+}
+
+export interface AddFavoriteParams {
+  item_id: number;
+  name: string;
+  price: number;
+  thumbnail_url: string | null;
+  category_name: string | null;
+  shop_name: string | null;
+}
+
+/** Outcome for one item of a bulk command */
+export interface BulkItemResult {
+  item_id: number;
+  /** false for no-ops, e.g. re-adding a favorite */
+  changed: boolean;
+  /** Set when this item failed and was rolled back; the others were kept */
+  error: AppError | null;
+}
+
+export interface ImportOptions {
+  /** User tags added to every imported item */
+  tags?: string[];
+  /** Collection every imported item is filed into */
+  collection_id?: number;
+  /** Locale to fetch item details in */
+  locale?: BoothLocale;
+}
+
+export interface ImportJob {
+  job_id: number;
+  /** Distinct item ids found in the input */
+  found: number;
+  /** Items that already were favorites; they are not fetched again */
+  duplicates: number[];
+}
+
+export type ImportStatus = 'added' | 'duplicate' | 'failed';
+
+export interface ImportProgress {
+  job_id: number;
+  item_id: number;
+  status: ImportStatus;
+  completed: number;
+  total: number;
+}
+
+export interface ImportSummary {
+  job_id: number;
+  found: number;
+  added: number[];
+  duplicates: number[];
+  failed: { item_id: number; error: AppError }[];
+}
+
+export type ExportFormat = 'csv' | 'json' | 'markdown';
+
+export interface FavoritesExport {
+  /** Suggested file name with a timestamp and the format's extension */
+  file_name: string;
+  mime_type: string;
+  content: string;
+  /** Exported favorites */
+  count: number;
+}
+
+export type FullTextScope = 'all' | 'favorites' | 'cache';
+
+export interface FullTextHit {
+  item_id: number;
+  name: string;
+  /** Excerpt with matches between HIGHLIGHT_START and HIGHLIGHT_END (see splitHighlights) */
+  snippet: string;
+  source: 'favorites' | 'cache';
+  favorite: boolean;
+  /** bm25 score; lower is better */
+  rank: number;
+}
+
+export interface OwnershipParams {
+  /** YYYY-MM-DD; defaults to today */
+  purchased_at?: string;
+  price_paid?: number;
+  variation?: string;
+  order_ref?: string;
 }
 
 export interface Collection {
@@ -57,10 +365,22 @@ export interface DashboardStats {
   collections_count: number;
   tags_count: number;
   searches_count: number;
+  /** Sum of favorites not marked as owned */
   total_value: number;
+  /** total_value counting each item's most expensive known variation */
+  total_value_max: number;
+  owned_count: number;
+  /** price_paid of owned items, or their listed price when not recorded */
+  total_spent: number;
   avg_price: number;
 }
 
+export interface ImageCacheUsage {
+  file_count: number;
+  total_bytes: number;
+  max_bytes: number;
+}
+
 export interface CategoryStat {
   category: string;
   count: number;
@@ -1,36 +1,70 @@
 import { useState, useMemo } from 'react';
-import { Search } from 'lucide-react';
+import { Search, RefreshCw, Upload, Download } from 'lucide-react';
 import FavoritesList from '../components/favorites/FavoritesList';
 import CollectionSidebar from '../components/favorites/CollectionSidebar';
-import { useFavorites } from '../hooks/useFavorites';
-import { useCollectionItems } from '../hooks/useCollections';
+import ImportPanel from '../components/favorites/ImportPanel';
+import { useFavorites, useFavoritesQuery } from '../hooks/useFavorites';
+import { useFavoritesRefresh } from '../hooks/useFavoritesRefresh';
+import { useToast } from '../lib/ToastContext';
 import { useI18n } from '../lib/i18n';
-import type { FavoriteItem } from '../lib/types';
+import { errorMessage } from '../lib/errors';
+import { exportFavorites } from '../lib/booth-api';
+import type { ExportFormat } from '../lib/types';
+
+/** Favorites loaded per "show more" click */
+const PAGE_SIZE = 100;
 
 export default function FavoritesPage() {
   const [selectedCollection, setSelectedCollection] = useState<number | null>(null);
   const [tagFilter, setTagFilter] = useState('');
+  const [limit, setLimit] = useState(PAGE_SIZE);
+  const [importOpen, setImportOpen] = useState(false);
   const { favorites } = useFavorites();
-  const collectionItemsQuery = useCollectionItems(selectedCollection);
   const { t, language } = useI18n();
+  const { refresh, isRefreshing, progress, autoRefresh, setAutoRefresh } = useFavoritesRefresh();
+  const { showToast } = useToast();
+
+  const handleRefresh = async () => {
+    const summary = await refresh();
+    if (summary) {
+      showToast(
+        `${summary.updated} ${t.favorites.refreshUpdated} · ${summary.unchanged} ${t.favorites.refreshUnchanged} · ${summary.failed} ${t.favorites.refreshFailed}`,
+      );
+    }
+  };
 
-  const baseItems: FavoriteItem[] =
-    selectedCollection != null ? (collectionItemsQuery.data ?? []) : favorites;
+  const filter = useMemo(
+    () => ({
+      text: tagFilter.trim() || undefined,
+      collection_id: selectedCollection ?? undefined,
+    }),
+    [tagFilter, selectedCollection],
+  );
+  const { data: page } = useFavoritesQuery(filter, 'added_desc', limit);
 
-  const displayItems = useMemo(() => {
-    if (!tagFilter.trim()) return baseItems;
-    const q = tagFilter.trim().toLowerCase();
-    return baseItems.filter(
-      (item) =>
-        item.name.toLowerCase().includes(q) ||
-        (item.shop_name?.toLowerCase().includes(q) ?? false) ||
-        (item.category_name?.toLowerCase().includes(q) ?? false),
-    );
-  }, [tagFilter, baseItems]);
+  const handleExport = async (format: ExportFormat) => {
+    try {
+      const file = await exportFavorites(format, filter);
+      const url = URL.createObjectURL(new Blob([file.content], { type: file.mime_type }));
+      const link = document.createElement('a');
+      link.href = url;
+      link.download = file.file_name;
+      link.click();
+      URL.revokeObjectURL(url);
+      showToast(`${file.count} ${t.favorites.exported}`);
+    } catch (e) {
+      console.error('Export failed:', e);
+      showToast(errorMessage(e, t));
+    }
+  };
+  const displayItems = page?.items ?? [];
+  const totalCount = page?.total_count ?? 0;
 
   const searchPlaceholder =
-    language === 'ko' ? '이름/샵/카테고리 검색...' : 'Search name/shop/category...';
-  const countText = language === 'ko' ? `${displayItems.length}건` : `${displayItems.length} items`;
+    language === 'ko'
+      ? '이름/샵/카테고리/메모/태그 검색...'
+      : 'Search name/shop/category/note/tag...';
+  const countText = language === 'ko' ? `${totalCount}건` : `${totalCount} items`;
 
   return (
     <div className="p-6">
@@ -41,7 +75,10 @@ export default function FavoritesPage() {
           {/* Sidebar */}
           <CollectionSidebar
             selected={selectedCollection}
-            onSelect={setSelectedCollection}
+            onSelect={(id) => {
+              setSelectedCollection(id);
+              setLimit(PAGE_SIZE);
+            }}
             totalCount={favorites.length}
           />
 
@@ -53,15 +90,71 @@ export default function FavoritesPage() {
                 <Search className="absolute left-2.5 top-1/2 -translate-y-1/2 w-4 h-4 text-gray-400" />
                 <input
                   value={tagFilter}
-                  onChange={(e) => setTagFilter(e.target.value)}
+                  onChange={(e) => {
+                    setTagFilter(e.target.value);
+                    setLimit(PAGE_SIZE);
+                  }}
                   placeholder={searchPlaceholder}
                   className="w-full pl-9 pr-3 py-2 text-sm border border-gray-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-indigo-500 focus:border-transparent"
                 />
               </div>
               <span className="text-sm text-gray-500">{countText}</span>
+              <div className="ml-auto flex items-center gap-3">
+                <label className="flex items-center gap-1.5 text-sm text-gray-600">
+                  <input
+                    type="checkbox"
+                    checked={autoRefresh}
+                    onChange={(e) => setAutoRefresh(e.target.checked)}
+                    className="rounded border-gray-300 text-indigo-600 focus:ring-indigo-500"
+                  />
+                  {t.favorites.autoRefresh}
+                </label>
+                <button
+                  onClick={() => setImportOpen((open) => !open)}
+                  className="flex items-center gap-1.5 px-3 py-1.5 text-sm text-gray-600 border border-gray-200 rounded-lg hover:bg-gray-50"
+                >
+                  <Upload className="w-4 h-4" />
+                  {t.favorites.importItems}
+                </button>
+                <label className="flex items-center gap-1.5 px-3 py-1.5 text-sm text-gray-600 border border-gray-200 rounded-lg hover:bg-gray-50">
+                  <Download className="w-4 h-4" />
+                  <select
+                    value=""
+                    onChange={(e) => handleExport(e.target.value as ExportFormat)}
+                    disabled={totalCount === 0}
+                    className="bg-transparent focus:outline-none"
+                  >
+                    <option value="" disabled>
+                      {t.favorites.export}
+                    </option>
+                    <option value="csv">CSV</option>
+                    <option value="json">JSON</option>
+                    <option value="markdown">Markdown</option>
+                  </select>
+                </label>
+                <button
+                  onClick={handleRefresh}
+                  disabled={isRefreshing || favorites.length === 0}
+                  className="flex items-center gap-1.5 px-3 py-1.5 text-sm text-gray-600 border border-gray-200 rounded-lg hover:bg-gray-50 disabled:opacity-50"
+                >
+                  <RefreshCw className={`w-4 h-4 ${isRefreshing ? 'animate-spin' : ''}`} />
+                  {progress ? `${progress.completed}/${progress.total}` : t.favorites.refresh}
+                </button>
+              </div>
             </div>
 
+            {importOpen && <ImportPanel onClose={() => setImportOpen(false)} />}
             <FavoritesList items={displayItems} />
+            {displayItems.length < totalCount && (
+              <div className="mt-6 flex justify-center">
+                <button
+                  onClick={() => setLimit((l) => l + PAGE_SIZE)}
+                  className="px-4 py-2 text-sm text-gray-600 border border-gray-200 rounded-lg hover:bg-gray-50"
+                >
+                  {t.favorites.showMore}
+                </button>
+              </div>
+            )}
           </div>
         </div>
       </div>
@@ -1,6 +1,6 @@
 import { useState, useEffect } from 'react';
 import { useParams, Link } from 'react-router-dom';
-import { useQuery } from '@tanstack/react-query';
+import { useQuery, useQueryClient } from '@tanstack/react-query';
 import {
   ArrowLeft,
   ExternalLink,
@@ -10,7 +10,8 @@ import {
   Languages,
   Loader2,
 } from 'lucide-react';
-import { getBoothItem } from '../lib/booth-api';
+import { cachedImageUrl, getCachedItem, onItemUpdated } from '../lib/booth-api';
+import type { BoothItem } from '../lib/types';
 import { useI18n } from '../lib/i18n';
 import { useFavorites } from '../hooks/useFavorites';
 import FavoriteButton from '../components/favorites/FavoriteButton';
@@ -54,10 +55,22 @@ export default function ItemDetailPage() {
     error,
   } = useQuery({
     queryKey: ['item', itemId],
-    queryFn: () => getBoothItem(itemId),
+    queryFn: async () => (await getCachedItem(itemId)).item,
     enabled: !isNaN(itemId),
   });
 
+  // Stale cached items are refreshed in the background; swap in the new data when it lands
+  const queryClient = useQueryClient();
+  useEffect(() => {
+    if (isNaN(itemId)) return;
+    const unlisten = onItemUpdated((updated) => {
+      if (updated.id === itemId) queryClient.setQueryData<BoothItem>(['item', itemId], updated);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [itemId, queryClient]);
+
   const backText = language === 'ko' ? '돌아가기' : 'Go back';
   const invalidIdText = language === 'ko' ? '잘못된 아이템 ID입니다.' : 'Invalid item ID.';
 
@@ -121,7 +134,15 @@ export default function ItemDetailPage() {
     );
   }
 
-  const priceText = item.price === 0 ? t.item.free : `¥${item.price.toLocaleString()}`;
+  const variationPrices = (item.variations ?? []).map((v) => v.price);
+  const minPrice = variationPrices.length > 0 ? Math.min(...variationPrices) : item.price;
+  const maxPrice = variationPrices.length > 0 ? Math.max(...variationPrices) : item.price;
+  const priceText =
+    maxPrice === 0
+      ? t.item.free
+      : minPrice === maxPrice
+        ? `¥${maxPrice.toLocaleString()}`
+        : `¥${minPrice.toLocaleString()} ~ ¥${maxPrice.toLocaleString()}`;
 
   return (
     <div className="p-6">
@@ -140,7 +161,7 @@ export default function ItemDetailPage() {
             <div className="aspect-square bg-gray-100 rounded-lg overflow-hidden relative">
               {item.images.length > 0 ? (
                 <img
-                  src={item.images[currentImage]}
+                  src={cachedImageUrl(item.images[currentImage])}
                   alt={item.name}
                   className="w-full h-full object-contain"
                 />
@@ -181,7 +202,7 @@ export default function ItemDetailPage() {
                       idx === currentImage ? 'border-indigo-500' : 'border-transparent'
                     }`}
                   >
-                    <img src={img} alt="" className="w-full h-full object-cover" />
+                    <img src={cachedImageUrl(img)} alt="" className="w-full h-full object-cover" />
                   </button>
                 ))}
               </div>
@@ -227,7 +248,7 @@ export default function ItemDetailPage() {
             </div>
 
             <p
-              className={`text-2xl font-bold mt-3 ${item.price === 0 ? 'text-green-600' : 'text-gray-900'}`}
+              className={`text-2xl font-bold mt-3 ${maxPrice === 0 ? 'text-green-600' : 'text-gray-900'}`}
             >
               {priceText}
             </p>
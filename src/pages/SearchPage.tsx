@@ -20,6 +20,7 @@ export default function SearchPage() {
     currentPage,
     isLoading,
     isEnriching,
+    isOffline,
     error,
     hasSearched,
     currentParams,
@@ -93,6 +94,14 @@ export default function SearchPage() {
             </div>
           ) : (
             <>
+              {isOffline && !isLoading && (
+                <p
+                  className="mb-4 rounded-lg bg-amber-50 px-4 py-2 text-sm text-amber-700"
+                  role="status"
+                >
+                  {t.search.offline}
+                </p>
+              )}
               <SearchResults
                 items={items}
                 isLoading={isLoading}
@@ -1,5 +1,5 @@
-import { Heart, FolderOpen, Tag, Search } from 'lucide-react';
-import { useStatistics } from '../hooks/useStatistics';
+import { Heart, FolderOpen, Tag, Search, Trash2 } from 'lucide-react';
+import { useImageCache, useStatistics } from '../hooks/useStatistics';
 import { useI18n } from '../lib/i18n';
 
 // ── Reusable bar component ────────────────────────────
@@ -70,6 +70,41 @@ function Section({ title, children }: { title: string; children: React.ReactNode
   );
 }
 
+// ── Image cache ───────────────────────────────────────
+
+const formatMb = (bytes: number) => `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+
+function ImageCacheSection() {
+  const { usage, clear, isClearing } = useImageCache();
+  const { t } = useI18n();
+
+  return (
+    <Section title={t.stats.imageCache}>
+      <div className="flex items-center gap-4">
+        <div className="flex-1">
+          <HorizontalBar
+            label={`${usage?.file_count ?? 0} ${t.stats.imageCacheFiles}`}
+            value={Math.round(((usage?.total_bytes ?? 0) / (usage?.max_bytes || 1)) * 100)}
+            maxValue={100}
+            color="bg-gray-500"
+          />
+          <p className="text-xs text-gray-400 mt-1 text-right">
+            {formatMb(usage?.total_bytes ?? 0)} / {formatMb(usage?.max_bytes ?? 0)}
+          </p>
+        </div>
+        <button
+          onClick={clear}
+          disabled={isClearing || !usage?.file_count}
+          className="flex items-center gap-1.5 px-3 py-1.5 text-sm text-gray-600 border border-gray-200 rounded-lg hover:bg-gray-50 disabled:opacity-50"
+        >
+          <Trash2 className="w-4 h-4" />
+          {t.stats.clearImageCache}
+        </button>
+      </div>
+    </Section>
+  );
+}
+
 // ── Page ──────────────────────────────────────────────
 
 export default function StatsPage() {
@@ -112,7 +147,14 @@ export default function StatsPage() {
             icon={Heart}
             label="즐겨찾기"
             value={stats?.favorites_count ?? 0}
-            sub={`총 ¥${(stats?.total_value ?? 0).toLocaleString()}`}
+            sub={
+              (stats && stats.total_value_max > stats.total_value
+                ? `총 ¥${stats.total_value.toLocaleString()} ~ ¥${stats.total_value_max.toLocaleString()}`
+                : `총 ¥${(stats?.total_value ?? 0).toLocaleString()}`) +
+              (stats?.owned_count
+                ? ` · ${t.stats.totalSpent} ¥${stats.total_spent.toLocaleString()}`
+                : '')
+            }
             color="bg-pink-500"
           />
           <StatCard
@@ -265,6 +307,8 @@ export default function StatsPage() {
             )}
           </Section>
         </div>
+
+        <ImageCacheSection />
       </div>
     </div>
   );
@@ -6,13 +6,13 @@ vi.mock("@tauri-apps/api/core", () => ({
   invoke: vi.fn(),
 }));
 
-// Mock @tauri-apps/api/event — used by App.tsx
+// Mock @tauri-apps/api/event — used by App.tsx, booth-api.ts
 vi.mock("@tauri-apps/api/event", () => ({
   listen: vi.fn(() => Promise.resolve(() => {})),
   emit: vi.fn(),
 }));
 
-// Mock @tauri-apps/plugin-http — used by booth-api.ts
+// Mock @tauri-apps/plugin-http — used by useTranslation.ts
 vi.mock("@tauri-apps/plugin-http", () => ({
   fetch: vi.fn(),
 }));